@@ -1,10 +1,17 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use juno::{
+    candle_transform::CandleTransformParams,
     filters::{Filters, Price, Size},
+    position_sizer::{self, PositionSizerParams},
+    slippage::{self, SlippageParams},
     stop_loss::{self, StopLossParams},
     strategies::{FourWeekRuleParams, StrategyParams},
     take_profit::{self, TakeProfitParams},
-    trading::{self, TradeInput, TraderParams, TradingParams},
+    time_exit::TimeExitParams,
+    trading::{
+        self, Direction, EntryOrder, ExecutionTiming, ExitTrigger, GapPolicy, Market,
+        ReEntryParams, SessionFilterParams, TradeInput, TraderParams, TradingParams,
+    },
     BorrowInfo, Candle, Fees, Interval, Timestamp,
 };
 
@@ -53,8 +60,15 @@ fn trade_benchmark(c: &mut Criterion) {
                     strategy,
                     stop_loss: StopLossParams::Noop(stop_loss::NoopParams {}),
                     take_profit: TakeProfitParams::Noop(take_profit::NoopParams {}),
+                    time_exit: TimeExitParams::default(),
+                    position_sizer: PositionSizerParams::AllIn(position_sizer::AllInParams {}),
+                    candle_transform: CandleTransformParams::default(),
                     trader: TraderParams {
                         interval: Interval::MIN_MS,
+                        direction: Direction::Both,
+                        margin_multiplier: 2,
+                        re_entry: ReEntryParams::default(),
+                        session: SessionFilterParams::default(),
                     },
                 },
                 &TradeInput {
@@ -62,10 +76,17 @@ fn trade_benchmark(c: &mut Criterion) {
                     fees: &fees,
                     filters: &filters,
                     borrow_info: &borrow_info,
-                    margin_multiplier: 2,
+                    maintenance_margin_level: 1.1,
                     quote: 1.0,
-                    long: true,
-                    short: true,
+                    exit_trigger: ExitTrigger::Close,
+                    execution_timing: ExecutionTiming::Close,
+                    entry_order: EntryOrder::Market,
+                    slippage: SlippageParams::Noop(slippage::NoopParams {}),
+                    market: Market::Spot,
+                    funding_rates: &[],
+                    blackouts: &[],
+                    gap_policy: GapPolicy::default(),
+                    record: false,
                 },
             )
         })
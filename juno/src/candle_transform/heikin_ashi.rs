@@ -0,0 +1,68 @@
+use super::CandleTransform;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct HeikinAshiParams {}
+
+#[derive(Clone)]
+pub struct HeikinAshi {
+    // Open and close of the previous Heikin-Ashi candle.
+    previous: Option<(f64, f64)>,
+}
+
+impl HeikinAshi {
+    pub fn new(_params: &HeikinAshiParams) -> Self {
+        Self { previous: None }
+    }
+}
+
+impl CandleTransform for HeikinAshi {
+    fn transform(&mut self, candle: &Candle) -> Candle {
+        let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+        let open = match self.previous {
+            Some((open, close)) => (open + close) / 2.0,
+            None => (candle.open + candle.close) / 2.0,
+        };
+        self.previous = Some((open, close));
+        Candle {
+            time: candle.time,
+            open,
+            high: candle.high.max(open).max(close),
+            low: candle.low.min(open).min(close),
+            close,
+            volume: candle.volume,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heikin_ashi() {
+        let mut transform = HeikinAshi::new(&HeikinAshiParams {});
+        let candle = |open, high, low, close| Candle {
+            time: 0.into(),
+            open,
+            high,
+            low,
+            close,
+            volume: 1.0,
+        };
+
+        let first = transform.transform(&candle(10.0, 14.0, 8.0, 12.0));
+        assert_eq!(
+            (first.open, first.high, first.low, first.close),
+            (11.0, 14.0, 8.0, 11.0)
+        );
+
+        let second = transform.transform(&candle(12.0, 13.0, 11.5, 12.5));
+        assert_eq!(
+            (second.open, second.high, second.low, second.close),
+            (11.0, 13.0, 11.0, 12.25)
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use super::CandleTransform;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+// Natural logarithm of all prices. Volume is left as is.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct LogPriceParams {}
+
+#[derive(Clone)]
+pub struct LogPrice {}
+
+impl LogPrice {
+    pub fn new(_params: &LogPriceParams) -> Self {
+        Self {}
+    }
+}
+
+impl CandleTransform for LogPrice {
+    fn transform(&mut self, candle: &Candle) -> Candle {
+        Candle {
+            open: candle.open.ln(),
+            high: candle.high.ln(),
+            low: candle.low.ln(),
+            close: candle.close.ln(),
+            ..*candle
+        }
+    }
+}
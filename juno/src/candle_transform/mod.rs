@@ -0,0 +1,46 @@
+mod heikin_ashi;
+mod log_price;
+mod regular;
+mod typical_price;
+
+pub use heikin_ashi::{HeikinAshi, HeikinAshiParams, HeikinAshiParamsContext};
+pub use log_price::{LogPrice, LogPriceParams, LogPriceParamsContext};
+pub use regular::{Regular, RegularParams, RegularParamsContext};
+pub use typical_price::{TypicalPrice, TypicalPriceParams, TypicalPriceParamsContext};
+
+use crate::{genetics::Chromosome, utils::box_clone, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+// Changes the candles a strategy sees. Orders are still filled at the real prices.
+pub trait CandleTransform: Send + Sync + CandleTransformClone {
+    fn transform(&mut self, candle: &Candle) -> Candle;
+}
+
+box_clone!(CandleTransform, CandleTransformClone);
+
+#[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum CandleTransformParams {
+    HeikinAshi(HeikinAshiParams),
+    LogPrice(LogPriceParams),
+    Regular(RegularParams),
+    TypicalPrice(TypicalPriceParams),
+}
+
+impl Default for CandleTransformParams {
+    fn default() -> Self {
+        Self::Regular(RegularParams {})
+    }
+}
+
+impl CandleTransformParams {
+    pub fn construct(&self) -> Box<dyn CandleTransform> {
+        match self {
+            Self::HeikinAshi(params) => Box::new(HeikinAshi::new(params)),
+            Self::LogPrice(params) => Box::new(LogPrice::new(params)),
+            Self::Regular(params) => Box::new(Regular::new(params)),
+            Self::TypicalPrice(params) => Box::new(TypicalPrice::new(params)),
+        }
+    }
+}
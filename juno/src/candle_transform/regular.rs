@@ -0,0 +1,22 @@
+use super::CandleTransform;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct RegularParams {}
+
+#[derive(Clone)]
+pub struct Regular {}
+
+impl Regular {
+    pub fn new(_params: &RegularParams) -> Self {
+        Self {}
+    }
+}
+
+impl CandleTransform for Regular {
+    fn transform(&mut self, candle: &Candle) -> Candle {
+        *candle
+    }
+}
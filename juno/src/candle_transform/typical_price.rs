@@ -0,0 +1,26 @@
+use super::CandleTransform;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+// Replaces close with the mean of high, low and close.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct TypicalPriceParams {}
+
+#[derive(Clone)]
+pub struct TypicalPrice {}
+
+impl TypicalPrice {
+    pub fn new(_params: &TypicalPriceParams) -> Self {
+        Self {}
+    }
+}
+
+impl CandleTransform for TypicalPrice {
+    fn transform(&mut self, candle: &Candle) -> Candle {
+        Candle {
+            close: (candle.high + candle.low + candle.close) / 3.0,
+            ..*candle
+        }
+    }
+}
@@ -52,6 +52,32 @@ impl Size {
         size
     }
 
+    // Splits `size` into as many orders as needed for none of them to exceed `max`.
+    pub fn split(&self, size: f64) -> Vec<f64> {
+        let mut sizes = Vec::new();
+        let mut remaining = size;
+        if self.max > 0.0 {
+            while remaining > self.max {
+                sizes.push(self.max);
+                remaining -= self.max;
+            }
+        }
+        sizes.push(remaining);
+        sizes
+    }
+
+    // Like `round_down` but splits `size` into multiple orders instead of capping it at `max`.
+    // The last order is dropped if it rounds down to zero.
+    pub fn round_down_split(&self, size: f64) -> Vec<f64> {
+        let mut sizes = self.split(size);
+        if let Some(last) = sizes.pop().map(|size| self.round_down(size)) {
+            if last > 0.0 {
+                sizes.push(last);
+            }
+        }
+        sizes
+    }
+
     pub fn round_up(&self, size: f64) -> f64 {
         let mut size = size;
         if size < self.min {
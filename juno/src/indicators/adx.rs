@@ -5,6 +5,7 @@ use super::{
 };
 use std::cmp::max;
 
+#[derive(Clone)]
 pub struct Adx {
     pub value: f64,
     dx: DX,
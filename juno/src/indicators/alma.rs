@@ -10,6 +10,7 @@ pub struct AlmaParams {
     pub sigma: Option<u32>, // Calculated from period if None.
 }
 
+#[derive(Clone)]
 pub struct Alma {
     pub value: f64,
 
@@ -0,0 +1,39 @@
+use super::{MAParams, MA};
+
+// Average true range. Wilder's original smoothing is `MAParams::Smma`.
+#[derive(Clone)]
+pub struct Atr {
+    pub value: f64,
+
+    ma: Box<dyn MA>,
+    previous_close: Option<f64>,
+}
+
+impl Atr {
+    pub fn new(ma: &MAParams) -> Self {
+        Self {
+            value: 0.0,
+            ma: ma.construct(),
+            previous_close: None,
+        }
+    }
+
+    pub fn maturity(&self) -> u32 {
+        self.ma.maturity()
+    }
+
+    pub fn mature(&self) -> bool {
+        self.ma.mature()
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) {
+        // The first candle has no previous close to gap from.
+        let true_range = match self.previous_close {
+            Some(previous_close) => f64::max(high, previous_close) - f64::min(low, previous_close),
+            None => high - low,
+        };
+        self.ma.update(true_range);
+        self.value = self.ma.value();
+        self.previous_close = Some(close);
+    }
+}
@@ -0,0 +1,64 @@
+use super::{MAParams, MA};
+use bounded_vec_deque::BoundedVecDeque;
+use std::cmp::max;
+
+// Bands `multiplier` standard deviations of the last `period` prices (the period of the MA)
+// above and below the MA.
+#[derive(Clone)]
+pub struct Bollinger {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+
+    ma: Box<dyn MA>,
+    prices: BoundedVecDeque<f64>,
+    multiplier: f64,
+}
+
+impl Bollinger {
+    pub fn new(ma: &MAParams, multiplier: f64) -> Self {
+        assert!(multiplier > 0.0);
+        Self {
+            upper: 0.0,
+            middle: 0.0,
+            lower: 0.0,
+            ma: ma.construct(),
+            prices: BoundedVecDeque::new(ma.period() as usize),
+            multiplier,
+        }
+    }
+
+    pub fn maturity(&self) -> u32 {
+        max(self.ma.maturity(), self.prices.max_len() as u32)
+    }
+
+    pub fn mature(&self) -> bool {
+        self.ma.mature() && self.prices.is_full()
+    }
+
+    // Distance between the bands relative to the middle band.
+    pub fn bandwidth(&self) -> f64 {
+        (self.upper - self.lower) / self.middle
+    }
+
+    pub fn update(&mut self, price: f64) {
+        self.ma.update(price);
+        self.prices.push_back(price);
+
+        if self.mature() {
+            let len = self.prices.len() as f64;
+            let mean = self.prices.iter().sum::<f64>() / len;
+            let variance = self
+                .prices
+                .iter()
+                .map(|price| (price - mean).powi(2))
+                .sum::<f64>()
+                / len;
+            let deviation = self.multiplier * variance.sqrt();
+
+            self.middle = self.ma.value();
+            self.upper = self.middle + deviation;
+            self.lower = self.middle - deviation;
+        }
+    }
+}
@@ -3,13 +3,14 @@ use super::{
     MA,
 };
 use serde::{Deserialize, Serialize};
-use std::cmp::min;
+use std::{borrow::Cow, cmp::min};
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct DemaParams {
     pub period: u32,
 }
 
+#[derive(Clone)]
 pub struct Dema {
     pub value: f64,
     ema1: Ema,
@@ -68,4 +69,12 @@ impl MA for Dema {
     fn value(&self) -> f64 {
         self.value
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("value".into(), self.value),
+            ("ema1".into(), self.ema1.value),
+            ("ema2".into(), self.ema2.value),
+        ]
+    }
 }
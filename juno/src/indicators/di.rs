@@ -1,6 +1,7 @@
 use super::dm::DM;
 use std::cmp::min;
 
+#[derive(Clone)]
 pub struct DI {
     pub plus_value: f64,
     pub minus_value: f64,
@@ -1,5 +1,6 @@
 use std::cmp::min;
 
+#[derive(Clone)]
 pub struct DM {
     pub plus_value: f64,
     pub minus_value: f64,
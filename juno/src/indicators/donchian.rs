@@ -0,0 +1,44 @@
+use crate::itertools::IteratorExt;
+use bounded_vec_deque::BoundedVecDeque;
+
+// Highest high and lowest low of the last `period` candles.
+#[derive(Clone)]
+pub struct Donchian {
+    pub upper: f64,
+    pub lower: f64,
+
+    highs: BoundedVecDeque<f64>,
+    lows: BoundedVecDeque<f64>,
+}
+
+impl Donchian {
+    pub fn new(period: u32) -> Self {
+        assert!(period > 0);
+        Self {
+            upper: 0.0,
+            lower: 0.0,
+            highs: BoundedVecDeque::new(period as usize),
+            lows: BoundedVecDeque::new(period as usize),
+        }
+    }
+
+    pub fn maturity(&self) -> u32 {
+        self.highs.max_len() as u32
+    }
+
+    pub fn mature(&self) -> bool {
+        self.highs.is_full()
+    }
+
+    pub fn middle(&self) -> f64 {
+        (self.upper + self.lower) / 2.0
+    }
+
+    pub fn update(&mut self, high: f64, low: f64) {
+        self.highs.push_back(high);
+        self.lows.push_back(low);
+
+        self.upper = self.highs.iter().minmax().1;
+        self.lower = self.lows.iter().minmax().0;
+    }
+}
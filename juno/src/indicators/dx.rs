@@ -1,6 +1,7 @@
 use super::dm::DM;
 use std::cmp::min;
 
+#[derive(Clone)]
 pub struct DX {
     pub value: f64,
     dm: DM,
@@ -9,6 +9,7 @@ pub struct EmaParams {
     pub smoothing: Option<f64>, // Calculated from period if None.
 }
 
+#[derive(Clone)]
 pub struct Ema {
     pub value: f64,
     a: f64,
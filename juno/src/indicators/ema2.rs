@@ -10,6 +10,7 @@ pub struct Ema2Params {
     pub period: u32,
 }
 
+#[derive(Clone)]
 pub struct Ema2 {
     pub value: f64,
     pub period: u32,
@@ -0,0 +1,94 @@
+use super::Donchian;
+use bounded_vec_deque::BoundedVecDeque;
+use std::cmp::{max, min};
+
+// Ichimoku Kinko Hyo. The senkou spans are plotted `displacement` candles ahead and the chikou
+// span the same number of candles behind.
+#[derive(Clone)]
+pub struct Ichimoku {
+    pub tenkan: f64,
+    pub kijun: f64,
+    // The cloud at the current candle, i.e. the spans as of `displacement` candles ago.
+    pub senkou_a: f64,
+    pub senkou_b: f64,
+    // The spans as of the current candle, i.e. the cloud `displacement` candles ahead.
+    pub leading_senkou_a: f64,
+    pub leading_senkou_b: f64,
+    // The current close, plotted behind.
+    pub chikou: f64,
+    // The close `displacement` candles ago, which the chikou span is plotted against.
+    pub displaced_close: f64,
+
+    tenkan_channel: Donchian,
+    kijun_channel: Donchian,
+    senkou_b_channel: Donchian,
+    leading_spans: BoundedVecDeque<(f64, f64)>,
+    closes: BoundedVecDeque<f64>,
+    t: u32,
+    t1: u32,
+    t2: u32,
+}
+
+impl Ichimoku {
+    pub fn new(
+        tenkan_period: u32,
+        kijun_period: u32,
+        senkou_b_period: u32,
+        displacement: u32,
+    ) -> Self {
+        assert!(displacement > 0);
+        let t1 = max(max(tenkan_period, kijun_period), senkou_b_period);
+        let t2 = t1 + displacement;
+        Self {
+            tenkan: 0.0,
+            kijun: 0.0,
+            senkou_a: 0.0,
+            senkou_b: 0.0,
+            leading_senkou_a: 0.0,
+            leading_senkou_b: 0.0,
+            chikou: 0.0,
+            displaced_close: 0.0,
+            tenkan_channel: Donchian::new(tenkan_period),
+            kijun_channel: Donchian::new(kijun_period),
+            senkou_b_channel: Donchian::new(senkou_b_period),
+            leading_spans: BoundedVecDeque::new(displacement as usize + 1),
+            closes: BoundedVecDeque::new(displacement as usize + 1),
+            t: 0,
+            t1,
+            t2,
+        }
+    }
+
+    pub fn maturity(&self) -> u32 {
+        self.t2
+    }
+
+    pub fn mature(&self) -> bool {
+        self.t >= self.t2
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) {
+        self.t = min(self.t + 1, self.t2);
+
+        self.tenkan_channel.update(high, low);
+        self.kijun_channel.update(high, low);
+        self.senkou_b_channel.update(high, low);
+        self.closes.push_back(close);
+
+        self.tenkan = self.tenkan_channel.middle();
+        self.kijun = self.kijun_channel.middle();
+        self.chikou = close;
+        self.displaced_close = self.closes[0];
+
+        if self.t >= self.t1 {
+            self.leading_senkou_a = (self.tenkan + self.kijun) / 2.0;
+            self.leading_senkou_b = self.senkou_b_channel.middle();
+            self.leading_spans
+                .push_back((self.leading_senkou_a, self.leading_senkou_b));
+
+            if self.t >= self.t2 {
+                (self.senkou_a, self.senkou_b) = self.leading_spans[0];
+            }
+        }
+    }
+}
@@ -1,18 +1,20 @@
 use super::MA;
 use bounded_vec_deque::BoundedVecDeque;
 use serde::{Deserialize, Serialize};
-use std::cmp::min;
+use std::{borrow::Cow, cmp::min};
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct KamaParams {
     pub period: u32,
 }
 
+#[derive(Clone)]
 pub struct Kama {
     pub value: f64,
 
     short_alpha: f64,
     long_alpha: f64,
+    efficiency_ratio: f64,
 
     prices: BoundedVecDeque<f64>,
     diffs: BoundedVecDeque<f64>,
@@ -30,6 +32,7 @@ impl Kama {
 
             short_alpha: 2.0 / (2.0 + 1.0),
             long_alpha: 2.0 / (30.0 + 1.0),
+            efficiency_ratio: 0.0,
 
             prices: BoundedVecDeque::new(params.period as usize),
             diffs: BoundedVecDeque::new(params.period as usize),
@@ -53,7 +56,7 @@ impl MA for Kama {
     fn update(&mut self, price: f64) {
         self.t = min(self.t + 1, self.t2);
 
-        if self.prices.len() > 0 {
+        if !self.prices.is_empty() {
             self.diffs
                 .push_back(f64::abs(price - self.prices[self.prices.len() - 1]));
         }
@@ -63,13 +66,13 @@ impl MA for Kama {
         } else if self.t >= self.t2 {
             // TODO: Can optimize this to keep track of sum separately.
             let diff_sum: f64 = self.diffs.iter().sum();
-            let er = if diff_sum == 0.0 {
+            self.efficiency_ratio = if diff_sum == 0.0 {
                 1.0
             } else {
                 f64::abs(price - self.prices[0]) / diff_sum
             };
             let sc = f64::powf(
-                er * (self.short_alpha - self.long_alpha) + self.long_alpha,
+                self.efficiency_ratio * (self.short_alpha - self.long_alpha) + self.long_alpha,
                 2.0,
             );
 
@@ -82,4 +85,11 @@ impl MA for Kama {
     fn value(&self) -> f64 {
         self.value
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("value".into(), self.value),
+            ("efficiency_ratio".into(), self.efficiency_ratio),
+        ]
+    }
 }
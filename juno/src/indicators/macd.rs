@@ -4,6 +4,7 @@ use super::{
 };
 use std::cmp::max;
 
+#[derive(Clone)]
 pub struct Macd {
     pub value: f64,
     pub signal: f64,
@@ -1,11 +1,15 @@
 mod adx;
 mod alma;
+mod atr;
+mod bollinger;
 mod dema;
 mod di;
 mod dm;
+mod donchian;
 mod dx;
 mod ema;
 mod ema2;
+mod ichimoku;
 mod kama;
 mod macd;
 mod rsi;
@@ -15,12 +19,16 @@ mod stoch;
 
 pub use adx::Adx;
 pub use alma::{Alma, AlmaParams};
+pub use atr::Atr;
+pub use bollinger::Bollinger;
 pub use dema::{Dema, DemaParams};
 pub use di::DI;
 pub use dm::DM;
+pub use donchian::Donchian;
 pub use dx::DX;
 pub use ema::{Ema, EmaParams};
 pub use ema2::{Ema2, Ema2Params};
+pub use ichimoku::Ichimoku;
 pub use kama::{Kama, KamaParams};
 pub use macd::Macd;
 pub use rsi::Rsi;
@@ -28,16 +36,24 @@ pub use sma::{Sma, SmaParams};
 pub use smma::{Smma, SmmaParams};
 pub use stoch::Stoch;
 
+use crate::utils::box_clone;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
-pub trait MA: Send + Sync {
+pub trait MA: Send + Sync + MAClone {
     fn maturity(&self) -> u32;
     fn mature(&self) -> bool;
     fn update(&mut self, price: f64);
     fn value(&self) -> f64;
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![("value".into(), self.value())]
+    }
 }
 
+box_clone!(MA, MAClone);
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum MAParams {
@@ -4,6 +4,7 @@ use super::{
 };
 use std::cmp::min;
 
+#[derive(Clone)]
 pub struct Rsi {
     pub value: f64,
 
@@ -7,6 +7,7 @@ pub struct SmaParams {
     pub period: u32,
 }
 
+#[derive(Clone)]
 pub struct Sma {
     pub value: f64,
     prices: Vec<f64>,
@@ -10,6 +10,7 @@ pub struct SmmaParams {
     pub period: u32,
 }
 
+#[derive(Clone)]
 pub struct Smma {
     pub value: f64,
 
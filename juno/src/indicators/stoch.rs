@@ -5,6 +5,7 @@ use super::{
 use bounded_vec_deque::BoundedVecDeque;
 use std::cmp::min;
 
+#[derive(Clone)]
 pub struct Stoch {
     pub k: f64,
     pub d: f64,
@@ -1,3 +1,4 @@
+pub mod candle_transform;
 pub mod clients;
 pub mod easing;
 pub mod filters;
@@ -5,10 +6,13 @@ pub mod genetics;
 pub mod indicators;
 pub mod itertools;
 pub mod math;
+pub mod position_sizer;
+pub mod slippage;
 pub mod statistics;
 pub mod stop_loss;
 pub mod strategies;
 pub mod take_profit;
+pub mod time_exit;
 pub mod trading;
 pub mod utils;
 
@@ -21,7 +25,7 @@ use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, ops::AddAssign};
 use strum::AsRefStr;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Advice {
     None,
     Long,
@@ -57,6 +61,13 @@ pub struct Candle {
     pub volume: f64,
 }
 
+// Funding rate of a perpetual futures contract, settled at `time`.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FundingRate {
+    pub time: Timestamp,
+    pub rate: f64,
+}
+
 impl AddAssign<&Candle> for Candle {
     fn add_assign(&mut self, other: &Self) {
         self.high = f64::max(self.high, other.high);
@@ -88,7 +99,7 @@ pub struct ExchangeInfo {
     pub borrow_info: HashMap<String, HashMap<String, BorrowInfo>>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct Fill {
     pub price: f64,
     pub size: f64,
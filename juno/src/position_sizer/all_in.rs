@@ -0,0 +1,22 @@
+use super::PositionSizer;
+use crate::genetics::Chromosome;
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct AllInParams {}
+
+#[derive(Clone)]
+pub struct AllIn {}
+
+impl AllIn {
+    pub fn new(_params: &AllInParams) -> Self {
+        Self {}
+    }
+}
+
+impl PositionSizer for AllIn {
+    fn quote(&self, quote: f64, _price: f64) -> f64 {
+        quote
+    }
+}
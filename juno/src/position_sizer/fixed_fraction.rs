@@ -0,0 +1,33 @@
+use super::PositionSizer;
+use crate::genetics::Chromosome;
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct FixedFractionParams {
+    pub fraction: f64,
+}
+
+fn fraction(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.010..1.000)
+}
+
+#[derive(Clone)]
+pub struct FixedFraction {
+    fraction: f64,
+}
+
+impl FixedFraction {
+    pub fn new(params: &FixedFractionParams) -> Self {
+        Self {
+            fraction: params.fraction,
+        }
+    }
+}
+
+impl PositionSizer for FixedFraction {
+    fn quote(&self, quote: f64, _price: f64) -> f64 {
+        quote * self.fraction
+    }
+}
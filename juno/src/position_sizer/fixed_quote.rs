@@ -0,0 +1,35 @@
+use super::PositionSizer;
+use crate::genetics::Chromosome;
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Commits the same amount to every position, expressed as a fraction of the initial quote so
+// that profits are not compounded.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct FixedQuoteParams {
+    pub fraction: f64,
+}
+
+fn fraction(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.010..1.000)
+}
+
+#[derive(Clone)]
+pub struct FixedQuote {
+    quote: f64,
+}
+
+impl FixedQuote {
+    pub fn new(params: &FixedQuoteParams, initial_quote: f64) -> Self {
+        Self {
+            quote: initial_quote * params.fraction,
+        }
+    }
+}
+
+impl PositionSizer for FixedQuote {
+    fn quote(&self, quote: f64, _price: f64) -> f64 {
+        f64::min(self.quote, quote)
+    }
+}
@@ -0,0 +1,98 @@
+use super::PositionSizer;
+use crate::genetics::Chromosome;
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Commits `fraction` of the Kelly optimal stake estimated from the positions closed so far. Until
+// `min_positions` have been closed, `fraction` of the available quote is committed instead.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct KellyParams {
+    pub fraction: f64,
+    pub min_positions: u32,
+}
+
+fn fraction(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.100..1.000)
+}
+fn min_positions(rng: &mut StdRng) -> u32 {
+    rng.gen_range(1..50)
+}
+
+#[derive(Clone)]
+pub struct Kelly {
+    fraction: f64,
+    min_positions: u32,
+    num_wins: u32,
+    num_losses: u32,
+    total_win: f64,
+    total_loss: f64,
+}
+
+impl Kelly {
+    pub fn new(params: &KellyParams) -> Self {
+        Self {
+            fraction: params.fraction,
+            min_positions: params.min_positions,
+            num_wins: 0,
+            num_losses: 0,
+            total_win: 0.0,
+            total_loss: 0.0,
+        }
+    }
+
+    fn optimal_fraction(&self) -> f64 {
+        let num_positions = self.num_wins + self.num_losses;
+        if num_positions < self.min_positions {
+            return 1.0;
+        }
+        let win_rate = self.num_wins as f64 / num_positions as f64;
+        if self.num_losses == 0 || self.total_loss == 0.0 {
+            return win_rate;
+        }
+        if self.num_wins == 0 {
+            return 0.0;
+        }
+        let payoff_ratio =
+            (self.total_win / self.num_wins as f64) / (self.total_loss / self.num_losses as f64);
+        f64::max(win_rate - (1.0 - win_rate) / payoff_ratio, 0.0)
+    }
+}
+
+impl PositionSizer for Kelly {
+    fn quote(&self, quote: f64, _price: f64) -> f64 {
+        quote * f64::min(self.fraction * self.optimal_fraction(), 1.0)
+    }
+
+    fn record(&mut self, roi: f64) {
+        if roi >= 0.0 {
+            self.num_wins += 1;
+            self.total_win += roi;
+        } else {
+            self.num_losses += 1;
+            self.total_loss -= roi;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fraction_of_optimal_stake() {
+        let mut sizer = Kelly::new(&KellyParams {
+            fraction: 0.5,
+            min_positions: 4,
+        });
+
+        // Fraction of the full quote is used during warm-up.
+        assert_eq!(sizer.quote(1.0, 1.0), 0.5);
+
+        // Win rate 0.75 and payoff ratio 1.0 give an optimal stake of 0.5.
+        for roi in [0.25, -0.25, 0.25, 0.25] {
+            sizer.record(roi);
+        }
+        assert_eq!(sizer.quote(1.0, 1.0), 0.25);
+    }
+}
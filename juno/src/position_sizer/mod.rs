@@ -0,0 +1,72 @@
+mod all_in;
+mod fixed_fraction;
+mod fixed_quote;
+mod kelly;
+mod pyramid;
+mod volatility;
+
+pub use all_in::{AllIn, AllInParams, AllInParamsContext};
+pub use fixed_fraction::{FixedFraction, FixedFractionParams, FixedFractionParamsContext};
+pub use fixed_quote::{FixedQuote, FixedQuoteParams, FixedQuoteParamsContext};
+pub use kelly::{Kelly, KellyParams, KellyParamsContext};
+pub use pyramid::{Pyramid, PyramidParams, PyramidParamsContext};
+pub use volatility::{Volatility, VolatilityParams, VolatilityParamsContext};
+
+use crate::{genetics::Chromosome, utils::box_clone, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+pub trait PositionSizer: Send + Sync + PositionSizerClone {
+    // Quote to commit to a new position entered at `price`, given the `quote` available. Zero
+    // means the entry is skipped.
+    fn quote(&self, quote: f64, price: f64) -> f64;
+
+    // Quote to add to the open position, given the `quote` available and the `change` of the price
+    // since entry in the direction of the position. Zero means nothing is added.
+    fn scale_in(&mut self, _quote: f64, _change: f64) -> f64 {
+        0.0
+    }
+
+    // Fraction of the open position to exit, given the `change` of the price since entry in the
+    // direction of the position. Zero means nothing is exited.
+    fn scale_out(&mut self, _change: f64) -> f64 {
+        0.0
+    }
+
+    fn update(&mut self, _candle: &Candle) {}
+
+    // Called with the return on investment of every closed position.
+    fn record(&mut self, _roi: f64) {}
+}
+
+box_clone!(PositionSizer, PositionSizerClone);
+
+#[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum PositionSizerParams {
+    AllIn(AllInParams),
+    FixedFraction(FixedFractionParams),
+    FixedQuote(FixedQuoteParams),
+    Kelly(KellyParams),
+    Pyramid(PyramidParams),
+    Volatility(VolatilityParams),
+}
+
+impl Default for PositionSizerParams {
+    fn default() -> Self {
+        Self::AllIn(AllInParams {})
+    }
+}
+
+impl PositionSizerParams {
+    pub fn construct(&self, initial_quote: f64) -> Box<dyn PositionSizer> {
+        match self {
+            Self::AllIn(params) => Box::new(AllIn::new(params)),
+            Self::FixedFraction(params) => Box::new(FixedFraction::new(params)),
+            Self::FixedQuote(params) => Box::new(FixedQuote::new(params, initial_quote)),
+            Self::Kelly(params) => Box::new(Kelly::new(params)),
+            Self::Pyramid(params) => Box::new(Pyramid::new(params)),
+            Self::Volatility(params) => Box::new(Volatility::new(params)),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use super::PositionSizer;
+use crate::genetics::Chromosome;
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Enters with `fraction` of the available quote. Every time the price moves another `step` in
+// favour of the position, adds `fraction` of the quote then available, up to `adds` times. Steps
+// beyond those exit `take` of what is left of the position instead.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct PyramidParams {
+    pub fraction: f64,
+    pub step: f64,
+    pub adds: u32,
+    pub take: f64,
+}
+
+fn fraction(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.010..1.000)
+}
+fn step(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.010..0.500)
+}
+fn adds(rng: &mut StdRng) -> u32 {
+    rng.gen_range(0..5)
+}
+fn take(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.000..0.900)
+}
+
+#[derive(Clone)]
+pub struct Pyramid {
+    fraction: f64,
+    step: f64,
+    adds: u32,
+    take: f64,
+    // Steps reached by the open position.
+    steps: u32,
+}
+
+impl Pyramid {
+    pub fn new(params: &PyramidParams) -> Self {
+        assert!(params.step > 0.0);
+        assert!((0.0..1.0).contains(&params.take));
+        Self {
+            fraction: params.fraction,
+            step: params.step,
+            adds: params.adds,
+            take: params.take,
+            steps: 0,
+        }
+    }
+
+    fn next_step_reached(&self, change: f64) -> bool {
+        change >= (self.steps + 1) as f64 * self.step
+    }
+}
+
+impl PositionSizer for Pyramid {
+    fn quote(&self, quote: f64, _price: f64) -> f64 {
+        quote * self.fraction
+    }
+
+    fn scale_in(&mut self, quote: f64, change: f64) -> f64 {
+        if self.steps < self.adds && self.next_step_reached(change) {
+            self.steps += 1;
+            quote * self.fraction
+        } else {
+            0.0
+        }
+    }
+
+    fn scale_out(&mut self, change: f64) -> f64 {
+        if self.steps >= self.adds && self.next_step_reached(change) {
+            self.steps += 1;
+            self.take
+        } else {
+            0.0
+        }
+    }
+
+    fn record(&mut self, _roi: f64) {
+        self.steps = 0;
+    }
+}
@@ -0,0 +1,82 @@
+use super::PositionSizer;
+use crate::{
+    genetics::Chromosome,
+    indicators::{Atr, MAParams, SmmaParams},
+    Candle,
+};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Scales the position so that a move of one average true range changes equity by `target`.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct VolatilityParams {
+    pub period: u32,
+    pub target: f64,
+}
+
+fn period(rng: &mut StdRng) -> u32 {
+    rng.gen_range(2..100)
+}
+fn target(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.001..0.100)
+}
+
+#[derive(Clone)]
+pub struct Volatility {
+    atr: Atr,
+    target: f64,
+}
+
+impl Volatility {
+    pub fn new(params: &VolatilityParams) -> Self {
+        Self {
+            atr: Atr::new(&MAParams::Smma(SmmaParams {
+                period: params.period,
+            })),
+            target: params.target,
+        }
+    }
+}
+
+impl PositionSizer for Volatility {
+    // No positions are entered until the average true range has matured.
+    fn quote(&self, quote: f64, price: f64) -> f64 {
+        if !self.atr.mature() || self.atr.value == 0.0 {
+            return 0.0;
+        }
+        f64::min(quote * self.target * price / self.atr.value, quote)
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.atr.update(candle.high, candle.low, candle.close);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scales_inversely_to_true_range() {
+        let mut sizer = Volatility::new(&VolatilityParams {
+            period: 2,
+            target: 0.01,
+        });
+        let candle = Candle {
+            time: 0.into(),
+            open: 100.0,
+            high: 102.0,
+            low: 98.0,
+            close: 100.0,
+            volume: 1.0,
+        };
+
+        for _ in 0..2 {
+            sizer.update(&candle);
+            assert_eq!(sizer.quote(1.0, 100.0), 0.0);
+        }
+        sizer.update(&candle);
+        assert_eq!(sizer.quote(1.0, 100.0), 0.25);
+    }
+}
@@ -36,7 +36,7 @@ static INTERVAL_FACTOR_MAP: Lazy<HashMap<&'static str, u64>> =
 static INTERVAL_GROUP_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+[a-zA-Z]+)").unwrap());
 
 fn calc_interval_group(group: &str) -> u64 {
-    for (i, c) in group.chars().enumerate() {
+    for (i, c) in group.char_indices() {
         if c.is_alphabetic() {
             return group[0..i].parse::<u64>().unwrap() * INTERVAL_FACTOR_MAP[&group[i..]];
         }
@@ -64,6 +64,18 @@ impl Timestamp {
         unimplemented!();
     }
 
+    // Hour of the day in UTC.
+    pub fn hour(&self) -> u32 {
+        datetime_utcfromtimestamp_ms(self.0).hour() as u32
+    }
+
+    // Day of the week in UTC, counted from Monday as 0.
+    pub fn weekday(&self) -> u32 {
+        datetime_utcfromtimestamp_ms(self.0)
+            .weekday()
+            .number_days_from_monday() as u32
+    }
+
     pub fn ceil(&self, interval: Interval) -> Self {
         if interval < Interval::WEEK_MS {
             return Self(ceil_multiple(self.0, interval.0));
@@ -224,6 +236,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_timestamp_hour_weekday() {
+        // A Tuesday.
+        let timestamp = Timestamp(1_546_300_800_000) + Interval::HOUR_MS * 13;
+        assert_eq!(timestamp.hour(), 13);
+        assert_eq!(timestamp.weekday(), 1);
+    }
+
     #[test]
     fn test_ceil_timestamp() {
         assert_eq!(
@@ -0,0 +1,28 @@
+use super::Slippage;
+use crate::Candle;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct FixedParams {
+    pub bps: f64,
+}
+
+#[derive(Clone)]
+pub struct Fixed {
+    rate: f64,
+}
+
+impl Fixed {
+    pub fn new(params: &FixedParams) -> Self {
+        assert!(params.bps >= 0.0);
+        Self {
+            rate: params.bps / 10_000.0,
+        }
+    }
+}
+
+impl Slippage for Fixed {
+    fn rate(&self, _candle: &Candle, _price: f64, _size: f64) -> f64 {
+        self.rate
+    }
+}
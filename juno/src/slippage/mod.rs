@@ -0,0 +1,55 @@
+mod fixed;
+mod noop;
+mod volatility;
+mod volume_participation;
+
+pub use fixed::{Fixed, FixedParams};
+pub use noop::{Noop, NoopParams};
+pub use volatility::{Volatility, VolatilityParams};
+pub use volume_participation::{VolumeParticipation, VolumeParticipationParams};
+
+use crate::{utils::box_clone, Candle};
+use serde::{Deserialize, Serialize};
+
+pub trait Slippage: Send + Sync + SlippageClone {
+    // Fraction of the reference price lost to slippage when filling `size` within `candle`.
+    fn rate(&self, _candle: &Candle, _price: f64, _size: f64) -> f64 {
+        0.0
+    }
+
+    fn buy_price(&self, candle: &Candle, price: f64, size: f64) -> f64 {
+        price * (1.0 + self.rate(candle, price, size))
+    }
+
+    fn sell_price(&self, candle: &Candle, price: f64, size: f64) -> f64 {
+        f64::max(price * (1.0 - self.rate(candle, price, size)), 0.0)
+    }
+}
+
+box_clone!(Slippage, SlippageClone);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum SlippageParams {
+    Fixed(FixedParams),
+    Noop(NoopParams),
+    Volatility(VolatilityParams),
+    VolumeParticipation(VolumeParticipationParams),
+}
+
+impl Default for SlippageParams {
+    fn default() -> Self {
+        Self::Noop(NoopParams {})
+    }
+}
+
+impl SlippageParams {
+    pub fn construct(&self) -> Box<dyn Slippage> {
+        match self {
+            Self::Fixed(params) => Box::new(Fixed::new(params)),
+            Self::Noop(params) => Box::new(Noop::new(params)),
+            Self::Volatility(params) => Box::new(Volatility::new(params)),
+            Self::VolumeParticipation(params) => Box::new(VolumeParticipation::new(params)),
+        }
+    }
+}
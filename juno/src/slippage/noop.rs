@@ -0,0 +1,16 @@
+use super::Slippage;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct NoopParams {}
+
+#[derive(Clone)]
+pub struct Noop {}
+
+impl Noop {
+    pub fn new(_params: &NoopParams) -> Self {
+        Self {}
+    }
+}
+
+impl Slippage for Noop {}
@@ -0,0 +1,54 @@
+use super::Slippage;
+use crate::Candle;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct VolatilityParams {
+    // Fraction of the candle's high-low range paid as slippage.
+    pub factor: f64,
+}
+
+#[derive(Clone)]
+pub struct Volatility {
+    factor: f64,
+}
+
+impl Volatility {
+    pub fn new(params: &VolatilityParams) -> Self {
+        assert!(params.factor >= 0.0);
+        Self {
+            factor: params.factor,
+        }
+    }
+}
+
+impl Slippage for Volatility {
+    fn rate(&self, candle: &Candle, price: f64, _size: f64) -> f64 {
+        if price == 0.0 {
+            0.0
+        } else {
+            self.factor * (candle.high - candle.low) / price
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_proportional_to_range() {
+        let target = Volatility::new(&VolatilityParams { factor: 0.5 });
+        let candle = Candle {
+            time: 0.into(),
+            open: 10.0,
+            high: 11.0,
+            low: 9.0,
+            close: 10.0,
+            volume: 1.0,
+        };
+        assert_eq!(target.rate(&candle, 10.0, 1.0), 0.1);
+        assert_eq!(target.buy_price(&candle, 10.0, 1.0), 11.0);
+        assert_eq!(target.sell_price(&candle, 10.0, 1.0), 9.0);
+    }
+}
@@ -0,0 +1,56 @@
+use super::Slippage;
+use crate::Candle;
+use serde::{Deserialize, Serialize};
+
+// Square-root market impact model based on the share of the candle's volume an order takes.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct VolumeParticipationParams {
+    pub impact: f64,
+}
+
+#[derive(Clone)]
+pub struct VolumeParticipation {
+    impact: f64,
+}
+
+impl VolumeParticipation {
+    pub fn new(params: &VolumeParticipationParams) -> Self {
+        assert!(params.impact >= 0.0);
+        Self {
+            impact: params.impact,
+        }
+    }
+}
+
+impl Slippage for VolumeParticipation {
+    fn rate(&self, candle: &Candle, _price: f64, size: f64) -> f64 {
+        // An order on a candle without volume is assumed to take all of it.
+        let participation = if candle.volume > 0.0 {
+            f64::min(size / candle.volume, 1.0)
+        } else {
+            1.0
+        };
+        self.impact * participation.sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_grows_with_participation() {
+        let target = VolumeParticipation::new(&VolumeParticipationParams { impact: 0.5 });
+        let candle = Candle {
+            time: 0.into(),
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 16.0,
+        };
+        assert_eq!(target.rate(&candle, 10.0, 1.0), 0.125);
+        assert_eq!(target.rate(&candle, 10.0, 4.0), 0.25);
+        assert_eq!(target.rate(&candle, 10.0, 64.0), 0.5);
+    }
+}
@@ -26,6 +26,14 @@ pub struct CoreStatistics {
     pub num_positions_in_loss: u32,
     pub num_stop_losses: u32,
     pub num_take_profits: u32,
+    #[serde(default)]
+    pub num_liquidations: u32,
+    #[serde(default)]
+    pub num_time_exits: u32,
+    #[serde(default)]
+    pub num_gaps: u32,
+    #[serde(default)]
+    pub num_missing_candles: u32,
 }
 
 impl CoreStatistics {
@@ -38,6 +46,8 @@ impl CoreStatistics {
         let mut num_positions_in_loss = 0;
         let mut num_stop_losses = 0;
         let mut num_take_profits = 0;
+        let mut num_liquidations = 0;
+        let mut num_time_exits = 0;
 
         // let mut drawdowns = Vec::with_capacity(self.positions.len());
         let mut max_drawdown = 0.0;
@@ -49,6 +59,7 @@ impl CoreStatistics {
             let (pos_profit, pos_duration, close_reason) = match pos {
                 Position::Long(pos) => (pos.profit(), pos.duration(), pos.close_reason),
                 Position::Short(pos) => (pos.profit(), pos.duration(), pos.close_reason),
+                Position::Futures(pos) => (pos.profit(), pos.duration(), pos.close_reason),
             };
 
             profit += pos_profit;
@@ -64,6 +75,10 @@ impl CoreStatistics {
                 num_stop_losses += 1;
             } else if close_reason == CloseReason::TakeProfit {
                 num_take_profits += 1;
+            } else if close_reason == CloseReason::Liquidation {
+                num_liquidations += 1;
+            } else if close_reason == CloseReason::TimeExit {
+                num_time_exits += 1;
             }
 
             quote += pos_profit;
@@ -116,6 +131,10 @@ impl CoreStatistics {
             num_positions_in_loss,
             num_stop_losses,
             num_take_profits,
+            num_liquidations,
+            num_time_exits,
+            num_gaps: summary.num_gaps,
+            num_missing_candles: summary.num_missing_candles,
         }
     }
 }
@@ -127,6 +146,7 @@ pub fn get_profit(summary: &TradingSummary) -> f64 {
         .map(|pos| match pos {
             Position::Long(pos) => pos.profit(),
             Position::Short(pos) => pos.profit(),
+            Position::Futures(pos) => pos.profit(),
         })
         .sum()
 }
@@ -141,6 +161,7 @@ pub fn get_return_over_max_drawdown(summary: &TradingSummary) -> f64 {
         let pos_profit = match pos {
             Position::Long(pos) => pos.profit(),
             Position::Short(pos) => pos.profit(),
+            Position::Futures(pos) => pos.profit(),
         };
 
         profit += pos_profit;
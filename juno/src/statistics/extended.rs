@@ -1,7 +1,7 @@
 use super::SQRT_365;
 use crate::{
     math::{mean, std_deviation},
-    trading::{Position, TradingSummary},
+    trading::{EquityRecord, Position, TradingSummary},
     Interval, SymbolExt, Timestamp,
 };
 // use ndarray::prelude::*;
@@ -50,6 +50,22 @@ impl ExtendedStatistics {
         // (alpha,)
         // portfolio_stats
     }
+
+    // Samples an equity curve, starting from `quote`, at the last record of every interval.
+    pub fn compose_equity(quote: f64, equity: &[EquityRecord], interval: Interval) -> Self {
+        let mut performance = vec![quote];
+        let mut period = None;
+        for record in equity {
+            let time = record.time.floor(interval);
+            if period == Some(time) {
+                *performance.last_mut().unwrap() = record.equity;
+            } else {
+                performance.push(record.equity);
+                period = Some(time);
+            }
+        }
+        calculate_statistics(&performance)
+    }
 }
 
 fn map_period_deltas_from_summary(
@@ -75,6 +91,15 @@ fn map_period_deltas_from_summary(
                 pos.base_cost(),
                 pos.gain(),
             ),
+            // Futures positions are settled in quote and never hold the base asset.
+            Position::Futures(pos) => (
+                pos.open_time,
+                pos.cost(),
+                0.0,
+                pos.close_time,
+                0.0,
+                pos.gain(),
+            ),
         };
         // Open.
         let time = time.floor(interval);
@@ -7,8 +7,8 @@ pub use extended::*;
 
 use crate::{
     math::annualized,
-    trading::{CloseReason, Position, TradingSummary},
-    Interval, Timestamp,
+    trading::{CloseReason, Halt, PortfolioSummary, Position, TradingSummary},
+    Advice, Interval, Timestamp,
 };
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +19,8 @@ pub(crate) const SQRT_365: f64 = 19.104_973_174_542_8;
 pub enum PositionType {
     Long,
     Short,
+    FuturesLong,
+    FuturesShort,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -73,6 +75,26 @@ impl PositionStatistics {
                     close_reason: pos.close_reason,
                 }
             }
+            Position::Futures(pos) => {
+                let duration = pos.duration();
+                let profit = pos.profit();
+                let roi = profit / pos.cost();
+                Self {
+                    type_: match pos.advice {
+                        Advice::Long => PositionType::FuturesLong,
+                        _ => PositionType::FuturesShort,
+                    },
+                    open_time: pos.open_time,
+                    close_time: pos.close_time,
+                    cost: pos.cost(),
+                    gain: pos.gain(),
+                    profit,
+                    duration,
+                    roi,
+                    annualized_roi: annualized(duration.0, roi),
+                    close_reason: pos.close_reason,
+                }
+            }
         }
     }
 }
@@ -82,6 +104,8 @@ pub struct Statistics {
     pub core: CoreStatistics,
     pub extended: ExtendedStatistics,
     pub positions: Vec<PositionStatistics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub halted: Option<Halt>,
 }
 
 impl Statistics {
@@ -99,6 +123,46 @@ impl Statistics {
                 .iter()
                 .map(PositionStatistics::compose)
                 .collect(),
+            halted: summary.halted,
+        }
+    }
+}
+
+// Statistics of the account a portfolio was traded from.
+#[derive(Serialize)]
+pub struct PortfolioStatistics {
+    pub core: CoreStatistics,
+    pub extended: ExtendedStatistics,
+    pub symbol_positions: HashMap<String, Vec<PositionStatistics>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub halted: Option<Halt>,
+}
+
+impl PortfolioStatistics {
+    pub fn compose(summary: &PortfolioSummary, stats_interval: Interval) -> Self {
+        let account = &summary.account;
+        Self {
+            core: CoreStatistics::compose(account),
+            extended: ExtendedStatistics::compose_equity(
+                account.quote,
+                &summary.equity,
+                stats_interval,
+            ),
+            symbol_positions: summary
+                .symbols
+                .iter()
+                .map(|(symbol, summary)| {
+                    (
+                        symbol.clone(),
+                        summary
+                            .positions
+                            .iter()
+                            .map(PositionStatistics::compose)
+                            .collect(),
+                    )
+                })
+                .collect(),
+            halted: account.halted,
         }
     }
 }
@@ -114,14 +178,14 @@ mod test_utils {
         let mut summary = TradingSummary::new(0.into(), 10.into(), 1.0);
         summary.positions.push(Position::Long(LongPosition {
             open_time: 2.into(),
-            open_fills: [Fill {
+            open_fills: vec![Fill {
                 price: 0.5,
                 size: 2.0,
                 quote: 1.0,
                 fee: 0.2,
             }],
             close_time: 4.into(),
-            close_fills: [Fill {
+            close_fills: vec![Fill {
                 price: 0.5,
                 size: 1.8,
                 quote: 0.9,
@@ -131,14 +195,14 @@ mod test_utils {
         }));
         summary.positions.push(Position::Long(LongPosition {
             open_time: 6.into(),
-            open_fills: [Fill {
+            open_fills: vec![Fill {
                 price: 0.5,
                 size: 1.62,
                 quote: 0.81,
                 fee: 0.02,
             }],
             close_time: 8.into(),
-            close_fills: [Fill {
+            close_fills: vec![Fill {
                 price: 0.75,
                 size: 1.6,
                 quote: 1.2,
@@ -0,0 +1,99 @@
+use super::StopLoss;
+use crate::{
+    genetics::Chromosome,
+    indicators::{MAExt, MAParams},
+    utils::AtrBand,
+    Candle,
+};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Stops out `multiplier` average true ranges, as of entry, away from the entry close. Inactive
+// for positions entered before the average true range has matured.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct AtrParams {
+    pub ma: MAParams,
+    pub multiplier: f64,
+}
+
+fn ma(rng: &mut StdRng) -> MAParams {
+    let period = rng.gen_range(2..100);
+    rng.gen_ma_params(period)
+}
+fn multiplier(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.5..5.0)
+}
+
+#[derive(Clone)]
+pub struct Atr {
+    band: AtrBand,
+    close: f64,
+}
+
+impl Atr {
+    pub fn new(params: &AtrParams) -> Self {
+        Self {
+            band: AtrBand::new(&params.ma, params.multiplier),
+            close: 0.0,
+        }
+    }
+}
+
+impl StopLoss for Atr {
+    fn upside_hit(&self) -> bool {
+        matches!(self.upside_price(), Some(price) if self.close <= price)
+    }
+
+    fn downside_hit(&self) -> bool {
+        matches!(self.downside_price(), Some(price) if self.close >= price)
+    }
+
+    fn upside_price(&self) -> Option<f64> {
+        self.band.lower()
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        self.band.upper()
+    }
+
+    fn clear(&mut self, candle: &Candle) {
+        self.band.clear(candle);
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.close = candle.close;
+        self.band.update(candle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{indicators::SmaParams, utils::hlc_candle};
+
+    #[test]
+    fn test_sides_and_hits() {
+        let mut stop_loss = Atr::new(&AtrParams {
+            ma: MAParams::Sma(SmaParams { period: 2 }),
+            multiplier: 2.0,
+        });
+        stop_loss.update(&hlc_candle(11.0, 9.0, 10.0));
+        // Average true range of 2.5.
+        let entry = hlc_candle(13.0, 12.0, 12.5);
+        stop_loss.update(&entry);
+        stop_loss.clear(&entry);
+        assert_eq!(stop_loss.upside_price(), Some(7.5));
+        assert_eq!(stop_loss.downside_price(), Some(17.5));
+
+        stop_loss.update(&hlc_candle(7.6, 7.6, 7.6));
+        assert!(!stop_loss.upside_hit());
+        stop_loss.update(&hlc_candle(7.5, 7.5, 7.5));
+        assert!(stop_loss.upside_hit());
+
+        stop_loss.update(&hlc_candle(17.4, 17.4, 17.4));
+        assert!(!stop_loss.downside_hit());
+        stop_loss.update(&hlc_candle(17.5, 17.5, 17.5));
+        assert!(stop_loss.downside_hit());
+    }
+}
@@ -17,6 +17,7 @@ fn down_threshold(rng: &mut StdRng) -> f64 {
     up_threshold(rng)
 }
 
+#[derive(Clone)]
 pub struct Basic {
     up_threshold_factor: f64,
     down_threshold_factor: f64,
@@ -44,6 +45,14 @@ impl StopLoss for Basic {
         self.close >= self.close_at_position * self.down_threshold_factor
     }
 
+    fn upside_price(&self) -> Option<f64> {
+        Some(self.close_at_position * self.up_threshold_factor)
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        Some(self.close_at_position * self.down_threshold_factor)
+    }
+
     fn clear(&mut self, candle: &Candle) {
         self.close_at_position = candle.close;
     }
@@ -1,6 +1,6 @@
 use super::{
-    Basic, BasicParams, BasicParamsContext, StopLoss, Trailing, TrailingParams,
-    TrailingParamsContext,
+    max_price, min_price, Basic, BasicParams, BasicParamsContext, StopLoss, Trailing,
+    TrailingParams, TrailingParamsContext,
 };
 use crate::{genetics::Chromosome, Candle};
 use juno_derive::*;
@@ -14,6 +14,7 @@ pub struct BasicPlusTrailingParams {
     pub trailing: TrailingParams,
 }
 
+#[derive(Clone)]
 pub struct BasicPlusTrailing {
     basic: Basic,
     trailing: Trailing,
@@ -37,6 +38,15 @@ impl StopLoss for BasicPlusTrailing {
         self.basic.downside_hit() || self.trailing.downside_hit()
     }
 
+    // The tighter of the two stops is the one hit first.
+    fn upside_price(&self) -> Option<f64> {
+        max_price(self.basic.upside_price(), self.trailing.upside_price())
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        min_price(self.basic.downside_price(), self.trailing.downside_price())
+    }
+
     fn clear(&mut self, candle: &Candle) {
         self.basic.clear(candle);
         self.trailing.clear(candle);
@@ -0,0 +1,123 @@
+use super::StopLoss;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Arms once the close has moved a threshold (a fraction of the entry close) in favor of the
+// position and from then on stops it out at the entry close moved an offset in its favor.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BreakEvenParams {
+    // Threshold and offset, drawn together so that the offset is the smaller. Otherwise the
+    // position would be closed as soon as the stop arms.
+    pub levels: (f64, f64),
+}
+
+fn levels(rng: &mut StdRng) -> (f64, f64) {
+    let threshold = rng.gen_range(0.001..0.500);
+    (threshold, rng.gen_range(0.000..threshold))
+}
+
+#[derive(Clone)]
+pub struct BreakEven {
+    threshold: f64,
+    offset: f64,
+    close_at_position: f64,
+    highest_close_since_position: f64,
+    lowest_close_since_position: f64,
+    close: f64,
+}
+
+impl BreakEven {
+    pub fn new(params: &BreakEvenParams) -> Self {
+        let (threshold, offset) = params.levels;
+        assert!(0.0 <= offset && offset < threshold);
+
+        Self {
+            threshold,
+            offset,
+            close_at_position: 0.0,
+            highest_close_since_position: 0.0,
+            lowest_close_since_position: f64::MAX,
+            close: 0.0,
+        }
+    }
+}
+
+impl StopLoss for BreakEven {
+    fn upside_hit(&self) -> bool {
+        matches!(self.upside_price(), Some(price) if self.close <= price)
+    }
+
+    fn downside_hit(&self) -> bool {
+        matches!(self.downside_price(), Some(price) if self.close >= price)
+    }
+
+    fn upside_price(&self) -> Option<f64> {
+        if self.highest_close_since_position >= self.close_at_position * (1.0 + self.threshold) {
+            Some(self.close_at_position * (1.0 + self.offset))
+        } else {
+            None
+        }
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        if self.lowest_close_since_position <= self.close_at_position * (1.0 - self.threshold) {
+            Some(self.close_at_position * (1.0 - self.offset))
+        } else {
+            None
+        }
+    }
+
+    fn clear(&mut self, candle: &Candle) {
+        self.close_at_position = candle.close;
+        self.highest_close_since_position = candle.close;
+        self.lowest_close_since_position = candle.close;
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.close = candle.close;
+        self.highest_close_since_position =
+            f64::max(self.highest_close_since_position, candle.close);
+        self.lowest_close_since_position = f64::min(self.lowest_close_since_position, candle.close);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(close: f64) -> Candle {
+        Candle {
+            time: 0.into(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_arms_after_threshold() {
+        let mut stop_loss = BreakEven::new(&BreakEvenParams {
+            levels: (0.1, 0.01),
+        });
+        stop_loss.clear(&candle(10.0));
+
+        // Not armed below the threshold, no matter how far the price falls.
+        stop_loss.update(&candle(10.5));
+        stop_loss.update(&candle(5.0));
+        assert_eq!(stop_loss.upside_price(), None);
+        assert!(!stop_loss.upside_hit());
+
+        stop_loss.clear(&candle(10.0));
+        stop_loss.update(&candle(11.0));
+        assert_eq!(stop_loss.upside_price(), Some(10.1));
+        assert!(!stop_loss.upside_hit());
+        stop_loss.update(&candle(10.1));
+        assert!(stop_loss.upside_hit());
+        // Shorts arm on the opposite move.
+        assert_eq!(stop_loss.downside_price(), None);
+    }
+}
@@ -0,0 +1,61 @@
+use super::{
+    max_price, min_price, BreakEven, BreakEvenParams, BreakEvenParamsContext, StopLoss, Trailing,
+    TrailingParams, TrailingParamsContext,
+};
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BreakEvenPlusTrailingParams {
+    #[chromosome]
+    pub break_even: BreakEvenParams,
+    #[chromosome]
+    pub trailing: TrailingParams,
+}
+
+#[derive(Clone)]
+pub struct BreakEvenPlusTrailing {
+    break_even: BreakEven,
+    trailing: Trailing,
+}
+
+impl BreakEvenPlusTrailing {
+    pub fn new(params: &BreakEvenPlusTrailingParams) -> Self {
+        Self {
+            break_even: BreakEven::new(&params.break_even),
+            trailing: Trailing::new(&params.trailing),
+        }
+    }
+}
+
+impl StopLoss for BreakEvenPlusTrailing {
+    fn upside_hit(&self) -> bool {
+        self.break_even.upside_hit() || self.trailing.upside_hit()
+    }
+
+    fn downside_hit(&self) -> bool {
+        self.break_even.downside_hit() || self.trailing.downside_hit()
+    }
+
+    fn upside_price(&self) -> Option<f64> {
+        max_price(self.break_even.upside_price(), self.trailing.upside_price())
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        min_price(
+            self.break_even.downside_price(),
+            self.trailing.downside_price(),
+        )
+    }
+
+    fn clear(&mut self, candle: &Candle) {
+        self.break_even.clear(candle);
+        self.trailing.clear(candle);
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.break_even.update(candle);
+        self.trailing.update(candle);
+    }
+}
@@ -0,0 +1,113 @@
+use super::StopLoss;
+use crate::{
+    genetics::Chromosome,
+    indicators::{self, MAExt, MAParams},
+    Candle,
+};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Chandelier exit. Trails `multiplier` current average true ranges below the highest high since
+// entry for longs and above the lowest low for shorts. Inactive until the average true range has
+// matured.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct ChandelierParams {
+    pub ma: MAParams,
+    pub multiplier: f64,
+}
+
+fn ma(rng: &mut StdRng) -> MAParams {
+    let period = rng.gen_range(2..100);
+    rng.gen_ma_params(period)
+}
+fn multiplier(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.5..5.0)
+}
+
+#[derive(Clone)]
+pub struct Chandelier {
+    atr: indicators::Atr,
+    multiplier: f64,
+    highest_high_since_position: f64,
+    lowest_low_since_position: f64,
+    close: f64,
+}
+
+impl Chandelier {
+    pub fn new(params: &ChandelierParams) -> Self {
+        Self {
+            atr: indicators::Atr::new(&params.ma),
+            multiplier: params.multiplier,
+            highest_high_since_position: 0.0,
+            lowest_low_since_position: f64::MAX,
+            close: 0.0,
+        }
+    }
+}
+
+impl StopLoss for Chandelier {
+    fn upside_hit(&self) -> bool {
+        matches!(self.upside_price(), Some(price) if self.close <= price)
+    }
+
+    fn downside_hit(&self) -> bool {
+        matches!(self.downside_price(), Some(price) if self.close >= price)
+    }
+
+    fn upside_price(&self) -> Option<f64> {
+        if self.atr.mature() {
+            Some(self.highest_high_since_position - self.multiplier * self.atr.value)
+        } else {
+            None
+        }
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        if self.atr.mature() {
+            Some(self.lowest_low_since_position + self.multiplier * self.atr.value)
+        } else {
+            None
+        }
+    }
+
+    fn clear(&mut self, candle: &Candle) {
+        self.highest_high_since_position = candle.high;
+        self.lowest_low_since_position = candle.low;
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.close = candle.close;
+        self.atr.update(candle.high, candle.low, candle.close);
+        self.highest_high_since_position = f64::max(self.highest_high_since_position, candle.high);
+        self.lowest_low_since_position = f64::min(self.lowest_low_since_position, candle.low);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{indicators::SmaParams, utils::hlc_candle};
+
+    #[test]
+    fn test_trails_highest_high_by_atr_multiple() {
+        let mut stop_loss = Chandelier::new(&ChandelierParams {
+            ma: MAParams::Sma(SmaParams { period: 2 }),
+            multiplier: 2.0,
+        });
+        stop_loss.update(&hlc_candle(11.0, 9.0, 10.0));
+        assert_eq!(stop_loss.upside_price(), None);
+        stop_loss.update(&hlc_candle(11.0, 9.0, 10.0));
+        stop_loss.clear(&hlc_candle(11.0, 9.0, 10.0));
+
+        // True range of 3 and a new high of 13.
+        stop_loss.update(&hlc_candle(13.0, 11.0, 12.0));
+        assert_eq!(stop_loss.upside_price(), Some(8.0));
+        assert!(!stop_loss.upside_hit());
+        // True range of 4 widens the stop to 13 - 2 * 3.5.
+        stop_loss.update(&hlc_candle(12.0, 8.0, 8.5));
+        assert_eq!(stop_loss.upside_price(), Some(6.0));
+        assert!(!stop_loss.upside_hit());
+        assert_eq!(stop_loss.downside_price(), Some(15.0));
+    }
+}
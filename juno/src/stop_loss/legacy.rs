@@ -22,6 +22,7 @@ fn trail(rng: &mut StdRng) -> bool {
     rng.gen_bool(0.5)
 }
 
+#[derive(Clone)]
 pub struct Legacy {
     pub threshold: f64,
     trail: bool,
@@ -65,6 +66,34 @@ impl StopLoss for Legacy {
                 } * (1.0 + self.threshold)
     }
 
+    fn upside_price(&self) -> Option<f64> {
+        if self.threshold > 0.0 {
+            Some(
+                if self.trail {
+                    self.highest_close_since_position
+                } else {
+                    self.close_at_position
+                } * (1.0 - self.threshold),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        if self.threshold > 0.0 {
+            Some(
+                if self.trail {
+                    self.lowest_close_since_position
+                } else {
+                    self.close_at_position
+                } * (1.0 + self.threshold),
+            )
+        } else {
+            None
+        }
+    }
+
     fn clear(&mut self, candle: &Candle) {
         self.close_at_position = candle.close;
         self.highest_close_since_position = candle.close;
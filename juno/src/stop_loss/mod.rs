@@ -1,22 +1,33 @@
+mod atr;
 mod basic;
 mod basic_plus_trailing;
+mod break_even;
+mod break_even_plus_trailing;
+mod chandelier;
 mod legacy;
 mod noop;
 mod trailing;
 
+pub use atr::{Atr, AtrParams, AtrParamsContext};
 pub use basic::{Basic, BasicParams, BasicParamsContext};
 pub use basic_plus_trailing::{
     BasicPlusTrailing, BasicPlusTrailingParams, BasicPlusTrailingParamsContext,
 };
+pub use break_even::{BreakEven, BreakEvenParams, BreakEvenParamsContext};
+pub use break_even_plus_trailing::{
+    BreakEvenPlusTrailing, BreakEvenPlusTrailingParams, BreakEvenPlusTrailingParamsContext,
+};
+pub use chandelier::{Chandelier, ChandelierParams, ChandelierParamsContext};
 pub use legacy::{Legacy, LegacyParams, LegacyParamsContext};
 pub use noop::{Noop, NoopParams, NoopParamsContext};
 pub use trailing::{Trailing, TrailingParams, TrailingParamsContext};
 
-use crate::{genetics::Chromosome, Candle};
+use crate::{genetics::Chromosome, utils::box_clone, Candle};
 use juno_derive::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
-pub trait StopLoss: Send + Sync {
+pub trait StopLoss: Send + Sync + StopLossClone {
     fn upside_hit(&self) -> bool {
         false
     }
@@ -25,16 +36,42 @@ pub trait StopLoss: Send + Sync {
         false
     }
 
+    // Price at or below which a long position is stopped out.
+    fn upside_price(&self) -> Option<f64> {
+        None
+    }
+
+    // Price at or above which a short position is stopped out.
+    fn downside_price(&self) -> Option<f64> {
+        None
+    }
+
     fn clear(&mut self, _candle: &Candle) {}
 
     fn update(&mut self, _candle: &Candle) {}
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        [
+            ("upside_price", self.upside_price()),
+            ("downside_price", self.downside_price()),
+        ]
+        .into_iter()
+        .filter_map(|(name, price)| price.map(|price| (name.into(), price)))
+        .collect()
+    }
 }
 
+box_clone!(StopLoss, StopLossClone);
+
 #[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum StopLossParams {
+    Atr(AtrParams),
     BasicPlusTrailing(BasicPlusTrailingParams),
     Basic(BasicParams),
+    BreakEven(BreakEvenParams),
+    BreakEvenPlusTrailing(BreakEvenPlusTrailingParams),
+    Chandelier(ChandelierParams),
     Legacy(LegacyParams),
     Noop(NoopParams),
     Trailing(TrailingParams),
@@ -43,11 +80,31 @@ pub enum StopLossParams {
 impl StopLossParams {
     pub fn construct(&self) -> Box<dyn StopLoss> {
         match self {
+            Self::Atr(params) => Box::new(Atr::new(params)),
             Self::BasicPlusTrailing(params) => Box::new(BasicPlusTrailing::new(params)),
             Self::Basic(params) => Box::new(Basic::new(params)),
+            Self::BreakEven(params) => Box::new(BreakEven::new(params)),
+            Self::BreakEvenPlusTrailing(params) => Box::new(BreakEvenPlusTrailing::new(params)),
+            Self::Chandelier(params) => Box::new(Chandelier::new(params)),
             Self::Legacy(params) => Box::new(Legacy::new(params)),
             Self::Noop(params) => Box::new(Noop::new(params)),
             Self::Trailing(params) => Box::new(Trailing::new(params)),
         }
     }
 }
+
+// The tighter of two long stops, i.e. the one hit first.
+fn max_price(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(f64::max(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+// The tighter of two short stops.
+fn min_price(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(f64::min(a, b)),
+        (a, b) => a.or(b),
+    }
+}
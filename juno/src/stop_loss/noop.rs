@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct NoopParams {}
 
+#[derive(Clone)]
 pub struct Noop {}
 
 impl Noop {
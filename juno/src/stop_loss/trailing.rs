@@ -17,6 +17,7 @@ fn down_threshold(rng: &mut StdRng) -> f64 {
     up_threshold(rng)
 }
 
+#[derive(Clone)]
 pub struct Trailing {
     up_threshold_factor: f64,
     down_threshold_factor: f64,
@@ -46,6 +47,14 @@ impl StopLoss for Trailing {
         self.close >= self.lowest_close_since_position * self.down_threshold_factor
     }
 
+    fn upside_price(&self) -> Option<f64> {
+        Some(self.highest_close_since_position * self.up_threshold_factor)
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        Some(self.lowest_close_since_position * self.down_threshold_factor)
+    }
+
     fn clear(&mut self, candle: &Candle) {
         self.highest_close_since_position = candle.close;
         self.lowest_close_since_position = candle.close;
@@ -0,0 +1,243 @@
+use super::{Signal, Strategy, StrategyMeta};
+use crate::{
+    genetics::Chromosome,
+    indicators::{self, MAExt, MAParams},
+    Advice, Candle,
+};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BollingerMode {
+    // Long on a close above the upper band, short on a close below the lower band. Liquidated
+    // once the close falls back past the middle band.
+    Breakout,
+    // Long on a close below the lower band, short on a close above the upper band. Liquidated
+    // once the close reaches the middle band.
+    MeanReversion,
+}
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BollingerParams {
+    pub ma: MAParams,
+    pub multiplier: f64,
+    pub mode: BollingerMode,
+    // If set, positions are only entered while the bandwidth of the previous candle is at most
+    // this, i.e. out of a squeeze.
+    #[serde(default)]
+    pub squeeze: Option<f64>,
+}
+
+fn ma(rng: &mut StdRng) -> MAParams {
+    let period = rng.gen_range(2..100);
+    rng.gen_ma_params(period)
+}
+fn multiplier(rng: &mut StdRng) -> f64 {
+    rng.gen_range(1.0..3.0)
+}
+fn mode(rng: &mut StdRng) -> BollingerMode {
+    if rng.gen_bool(0.5) {
+        BollingerMode::Breakout
+    } else {
+        BollingerMode::MeanReversion
+    }
+}
+fn squeeze(rng: &mut StdRng) -> Option<f64> {
+    if rng.gen_bool(0.5) {
+        Some(rng.gen_range(0.01..0.5))
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Signal)]
+pub struct Bollinger {
+    bollinger: indicators::Bollinger,
+    mode: BollingerMode,
+    squeeze: Option<f64>,
+    previous_bandwidth: Option<f64>,
+    advice: Advice,
+}
+
+impl Bollinger {
+    pub fn new(params: &BollingerParams, _meta: &StrategyMeta) -> Self {
+        Self {
+            bollinger: indicators::Bollinger::new(&params.ma, params.multiplier),
+            mode: params.mode,
+            squeeze: params.squeeze,
+            previous_bandwidth: None,
+            advice: Advice::None,
+        }
+    }
+
+    fn squeezed(&self) -> bool {
+        match (self.squeeze, self.previous_bandwidth) {
+            (Some(squeeze), Some(bandwidth)) => bandwidth <= squeeze,
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+impl Strategy for Bollinger {
+    fn maturity(&self) -> u32 {
+        self.bollinger.maturity()
+    }
+
+    fn mature(&self) -> bool {
+        self.bollinger.mature()
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.bollinger.update(candle.close);
+
+        if self.bollinger.mature() {
+            let (upper, middle, lower) = (
+                self.bollinger.upper,
+                self.bollinger.middle,
+                self.bollinger.lower,
+            );
+            let (long, short) = match self.mode {
+                BollingerMode::Breakout => (candle.close > upper, candle.close < lower),
+                BollingerMode::MeanReversion => (candle.close < lower, candle.close > upper),
+            };
+            let (exit_long, exit_short) = match self.mode {
+                BollingerMode::Breakout => (candle.close < middle, candle.close > middle),
+                BollingerMode::MeanReversion => (candle.close >= middle, candle.close <= middle),
+            };
+
+            if long && self.squeezed() {
+                self.advice = Advice::Long;
+            } else if short && self.squeezed() {
+                self.advice = Advice::Short;
+            } else if (self.advice == Advice::Long && exit_long)
+                || (self.advice == Advice::Short && exit_short)
+            {
+                self.advice = Advice::Liquidate;
+            }
+
+            self.previous_bandwidth = Some(self.bollinger.bandwidth());
+        }
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("upper".into(), self.bollinger.upper),
+            ("middle".into(), self.bollinger.middle),
+            ("lower".into(), self.bollinger.lower),
+            ("bandwidth".into(), self.bollinger.bandwidth()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{indicators::SmaParams, Interval};
+
+    // Bands one standard deviation around the SMA of the last 4 closes.
+    fn run(mode: BollingerMode, squeeze: Option<f64>, closes: &[f64]) -> Vec<Advice> {
+        let mut strategy = Bollinger::new(
+            &BollingerParams {
+                ma: MAParams::Sma(SmaParams { period: 4 }),
+                multiplier: 1.0,
+                mode,
+                squeeze,
+            },
+            &StrategyMeta {
+                interval: Interval::DAY_MS,
+            },
+        );
+        closes
+            .iter()
+            .map(|&close| {
+                strategy.update(&Candle {
+                    time: 0.into(),
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    volume: 1.0,
+                });
+                strategy.advice()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_breakout() {
+        assert_eq!(
+            run(
+                BollingerMode::Breakout,
+                None,
+                &[10.0, 10.0, 10.0, 10.0, 11.0, 11.0, 10.0]
+            ),
+            [
+                Advice::None,
+                Advice::None,
+                Advice::None,
+                Advice::None,
+                // Above the upper band of ~10.683 (10.25 + ~0.433).
+                Advice::Long,
+                // On the upper band of 11 (10.5 + 0.5).
+                Advice::Long,
+                // Below the middle band of 10.5 but not the lower band of 10.
+                Advice::Liquidate,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mean_reversion() {
+        assert_eq!(
+            run(
+                BollingerMode::MeanReversion,
+                None,
+                &[10.0, 10.0, 10.0, 10.0, 11.0, 11.0, 10.0]
+            ),
+            [
+                Advice::None,
+                Advice::None,
+                Advice::None,
+                Advice::None,
+                // Above the upper band of ~10.683.
+                Advice::Short,
+                Advice::Short,
+                // Back to below the middle band of 10.5.
+                Advice::Liquidate,
+            ]
+        );
+        assert_eq!(
+            run(
+                BollingerMode::MeanReversion,
+                None,
+                &[10.0, 10.0, 10.0, 10.0, 6.0, 6.0, 8.0]
+            )[4..],
+            // Below the lower band of ~7.268 (9 - ~1.732), then above the middle band of 7.5.
+            [Advice::Long, Advice::Long, Advice::Liquidate]
+        );
+    }
+
+    #[test]
+    fn test_squeeze() {
+        // Out of a flat window with a bandwidth of 0.
+        assert_eq!(
+            run(
+                BollingerMode::Breakout,
+                Some(0.05),
+                &[10.0, 10.0, 10.0, 10.0, 11.0]
+            )[4],
+            Advice::Long
+        );
+        // Out of a window of 10, 12, 10, 12 with a bandwidth of ~0.182 (2 / 11). The close of 14
+        // is above the upper band of ~13.414 (12 + ~1.414).
+        let closes = [10.0, 12.0, 10.0, 12.0, 14.0];
+        assert_eq!(run(BollingerMode::Breakout, None, &closes)[4], Advice::Long);
+        assert_eq!(
+            run(BollingerMode::Breakout, Some(0.05), &closes)[4],
+            Advice::None
+        );
+    }
+}
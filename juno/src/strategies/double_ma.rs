@@ -1,4 +1,4 @@
-use super::{Signal, Strategy, StrategyMeta};
+use super::{prefixed, Signal, Strategy, StrategyMeta};
 use crate::{
     genetics::Chromosome,
     indicators::{MAExt, MAParams, MA},
@@ -7,7 +7,7 @@ use crate::{
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::max;
+use std::{borrow::Cow, cmp::max};
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct DoubleMAParams {
@@ -24,7 +24,7 @@ fn mas(rng: &mut StdRng) -> (MAParams, MAParams) {
     }
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct DoubleMA {
     short_ma: Box<dyn MA>,
     long_ma: Box<dyn MA>,
@@ -69,4 +69,10 @@ impl Strategy for DoubleMA {
             }
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        let mut values = prefixed("short_ma", self.short_ma.debug_values());
+        values.extend(prefixed("long_ma", self.long_ma.debug_values()));
+        values
+    }
 }
@@ -1,4 +1,4 @@
-use super::{Signal, Strategy, StrategyMeta};
+use super::{prefixed, Signal, Strategy, StrategyMeta};
 use crate::{
     genetics::Chromosome,
     indicators::{MAExt, MAParams, MA},
@@ -7,7 +7,7 @@ use crate::{
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::max;
+use std::{borrow::Cow, cmp::max};
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct DoubleMA2Params {
@@ -31,7 +31,7 @@ fn pos_threshold(rng: &mut StdRng) -> f64 {
     rng.gen_range(0.1..1.0)
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct DoubleMA2 {
     short_ma: Box<dyn MA>,
     long_ma: Box<dyn MA>,
@@ -85,4 +85,10 @@ impl Strategy for DoubleMA2 {
             }
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        let mut values = prefixed("short_ma", self.short_ma.debug_values());
+        values.extend(prefixed("long_ma", self.long_ma.debug_values()));
+        values
+    }
 }
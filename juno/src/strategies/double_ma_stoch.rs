@@ -1,4 +1,4 @@
-use super::{Oscillator, Signal, Strategy, StrategyMeta};
+use super::{prefixed, Oscillator, Signal, Strategy, StrategyMeta};
 use crate::{
     genetics::Chromosome,
     strategies::{
@@ -8,7 +8,7 @@ use crate::{
 };
 use juno_derive::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::max;
+use std::{borrow::Cow, cmp::max};
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct DoubleMAStochParams {
@@ -18,7 +18,7 @@ pub struct DoubleMAStochParams {
     pub stoch: StochParams,
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct DoubleMAStoch {
     double_ma: DoubleMA,
     stoch: Stoch,
@@ -73,4 +73,45 @@ impl Strategy for DoubleMAStoch {
             }
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        let mut values = prefixed("double_ma", self.double_ma.debug_values());
+        values.extend(prefixed("stoch", self.stoch.debug_values()));
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interval;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_debug_values_prefixed_per_component() {
+        let params = DoubleMAStochParams::generate(
+            &mut StdRng::seed_from_u64(0),
+            &DoubleMAStochParamsContext::default(),
+        );
+        let strategy = DoubleMAStoch::new(
+            &params,
+            &StrategyMeta {
+                interval: Interval::DAY_MS,
+            },
+        );
+        let names: Vec<_> = strategy
+            .debug_values()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "double_ma.short_ma.value",
+                "double_ma.long_ma.value",
+                "stoch.k",
+                "stoch.d",
+            ]
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use super::{Signal, Strategy, StrategyMeta};
+use super::{prefixed, Signal, Strategy, StrategyMeta};
 use crate::{
     genetics::Chromosome,
     indicators::{self, MAExt, MAParams},
@@ -10,7 +10,7 @@ use indicators::EmaParams;
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::min;
+use std::{borrow::Cow, cmp::min};
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct FourWeekRuleParams {
@@ -40,7 +40,7 @@ fn ma(rng: &mut StdRng) -> MAParams {
 
 // We can use https://github.com/dtolnay/typetag to serialize a Box<dyn trait> if needed. Otherwise,
 // turn it into a generic and use a macro to generate all variations.
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct FourWeekRule {
     prices: BoundedVecDeque<f64>,
     ma: Box<dyn indicators::MA>,
@@ -91,4 +91,13 @@ impl Strategy for FourWeekRule {
 
         self.prices.push_back(candle.close);
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        // The channel the next close breaks out of. NaN until the first price is in.
+        let lowest = self.prices.iter().copied().fold(f64::NAN, f64::min);
+        let highest = self.prices.iter().copied().fold(f64::NAN, f64::max);
+        let mut values = vec![("lowest".into(), lowest), ("highest".into(), highest)];
+        values.extend(prefixed("ma", self.ma.debug_values()));
+        values
+    }
 }
@@ -0,0 +1,276 @@
+use super::{Signal, Strategy, StrategyMeta};
+use crate::{genetics::Chromosome, indicators, Advice, Candle};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum IchimokuMode {
+    // Long while the tenkan is above the kijun, short while below.
+    TkCross,
+    // Long while the close is above the cloud, short while below. Liquidated inside the cloud.
+    PriceCloud,
+    // Long while the leading senkou A is above the leading senkou B, short while below.
+    CloudTwist,
+}
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct IchimokuParams {
+    // Tenkan, kijun and senkou B periods, drawn together so that they are in increasing order.
+    pub periods: (u32, u32, u32),
+    pub displacement: u32,
+    pub mode: IchimokuMode,
+    // If set, a position is only held while the chikou span confirms it, i.e. the close is past
+    // the close `displacement` candles ago in its direction. Liquidated otherwise.
+    #[serde(default)]
+    pub chikou: bool,
+}
+
+impl Default for IchimokuParams {
+    fn default() -> Self {
+        Self {
+            periods: (9, 26, 52),
+            displacement: 26,
+            mode: IchimokuMode::TkCross,
+            chikou: false,
+        }
+    }
+}
+
+fn periods(rng: &mut StdRng) -> (u32, u32, u32) {
+    let tenkan_period = rng.gen_range(2..50);
+    let kijun_period = rng.gen_range(tenkan_period + 1..100);
+    (
+        tenkan_period,
+        kijun_period,
+        rng.gen_range(kijun_period + 1..200),
+    )
+}
+fn displacement(rng: &mut StdRng) -> u32 {
+    rng.gen_range(1..100)
+}
+fn mode(rng: &mut StdRng) -> IchimokuMode {
+    match rng.gen_range(0..3) {
+        0 => IchimokuMode::TkCross,
+        1 => IchimokuMode::PriceCloud,
+        _ => IchimokuMode::CloudTwist,
+    }
+}
+fn chikou(rng: &mut StdRng) -> bool {
+    rng.gen_bool(0.5)
+}
+
+#[derive(Clone, Signal)]
+pub struct Ichimoku {
+    ichimoku: indicators::Ichimoku,
+    mode: IchimokuMode,
+    chikou: bool,
+    advice: Advice,
+}
+
+impl Ichimoku {
+    pub fn new(params: &IchimokuParams, _meta: &StrategyMeta) -> Self {
+        let (tenkan_period, kijun_period, senkou_b_period) = params.periods;
+        assert!(tenkan_period < kijun_period && kijun_period < senkou_b_period);
+
+        Self {
+            ichimoku: indicators::Ichimoku::new(
+                tenkan_period,
+                kijun_period,
+                senkou_b_period,
+                params.displacement,
+            ),
+            mode: params.mode,
+            chikou: params.chikou,
+            advice: Advice::None,
+        }
+    }
+}
+
+impl Strategy for Ichimoku {
+    fn maturity(&self) -> u32 {
+        self.ichimoku.maturity()
+    }
+
+    fn mature(&self) -> bool {
+        self.ichimoku.mature()
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.ichimoku.update(candle.high, candle.low, candle.close);
+
+        if self.ichimoku.mature() {
+            let ichimoku = &self.ichimoku;
+            let (long, short) = match self.mode {
+                IchimokuMode::TkCross => (
+                    ichimoku.tenkan > ichimoku.kijun,
+                    ichimoku.tenkan < ichimoku.kijun,
+                ),
+                IchimokuMode::PriceCloud => (
+                    candle.close > f64::max(ichimoku.senkou_a, ichimoku.senkou_b),
+                    candle.close < f64::min(ichimoku.senkou_a, ichimoku.senkou_b),
+                ),
+                IchimokuMode::CloudTwist => (
+                    ichimoku.leading_senkou_a > ichimoku.leading_senkou_b,
+                    ichimoku.leading_senkou_a < ichimoku.leading_senkou_b,
+                ),
+            };
+            let advice = if long {
+                Advice::Long
+            } else if short {
+                Advice::Short
+            } else if self.mode == IchimokuMode::PriceCloud {
+                Advice::Liquidate
+            } else {
+                self.advice
+            };
+
+            let confirmed = match advice {
+                Advice::Long => ichimoku.chikou > ichimoku.displaced_close,
+                Advice::Short => ichimoku.chikou < ichimoku.displaced_close,
+                _ => true,
+            };
+            self.advice = if self.chikou && !confirmed {
+                Advice::Liquidate
+            } else {
+                advice
+            };
+        }
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("tenkan".into(), self.ichimoku.tenkan),
+            ("kijun".into(), self.ichimoku.kijun),
+            ("senkou_a".into(), self.ichimoku.senkou_a),
+            ("senkou_b".into(), self.ichimoku.senkou_b),
+            ("chikou".into(), self.ichimoku.chikou),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interval;
+
+    // Periods of 1, 2 and 3 candles. Candles are flat at their close, so the tenkan is the close,
+    // the kijun the middle of the last two closes and the senkou B the middle of the last three.
+    fn run(mode: IchimokuMode, displacement: u32, chikou: bool, closes: &[f64]) -> Vec<Advice> {
+        let mut strategy = Ichimoku::new(
+            &IchimokuParams {
+                periods: (1, 2, 3),
+                displacement,
+                mode,
+                chikou,
+            },
+            &StrategyMeta {
+                interval: Interval::DAY_MS,
+            },
+        );
+        closes
+            .iter()
+            .map(|&close| {
+                strategy.update(&Candle {
+                    time: 0.into(),
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    volume: 1.0,
+                });
+                strategy.advice()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tk_cross() {
+        assert_eq!(
+            run(
+                IchimokuMode::TkCross,
+                1,
+                false,
+                &[10.0, 10.0, 10.0, 12.0, 8.0, 8.0]
+            ),
+            [
+                Advice::None,
+                Advice::None,
+                Advice::None,
+                // Tenkan 12 over kijun 11.
+                Advice::Long,
+                // Tenkan 8 under kijun 10.
+                Advice::Short,
+                // Tenkan and kijun both at 8 keep the previous advice.
+                Advice::Short,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_price_cloud() {
+        assert_eq!(
+            run(
+                IchimokuMode::PriceCloud,
+                1,
+                false,
+                &[10.0, 10.0, 10.0, 10.0, 12.0, 11.0, 8.0]
+            ),
+            [
+                Advice::None,
+                Advice::None,
+                Advice::None,
+                // Close 10 on a flat cloud at 10.
+                Advice::Liquidate,
+                // Close 12 above the cloud between 10 and 10.
+                Advice::Long,
+                // Close 11 inside the cloud between 11 (senkou B) and 11.5 (senkou A).
+                Advice::Liquidate,
+                // Close 8 below the cloud between 11 (senkou B) and 11.25 (senkou A).
+                Advice::Short,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cloud_twist() {
+        assert_eq!(
+            run(
+                IchimokuMode::CloudTwist,
+                1,
+                false,
+                &[10.0, 10.0, 10.0, 10.0, 12.0, 9.0, 9.0]
+            ),
+            [
+                Advice::None,
+                Advice::None,
+                Advice::None,
+                // Leading spans both at 10.
+                Advice::None,
+                // Leading senkou A 11.5 over leading senkou B 11.
+                Advice::Long,
+                // Leading senkou A 9.75 under leading senkou B 10.5.
+                Advice::Short,
+                // Leading senkou A 9 under leading senkou B 10.5.
+                Advice::Short,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chikou_filter() {
+        let closes = [10.0, 10.0, 10.0, 10.0, 12.0, 11.5];
+        // Tenkan 11.5 under kijun 11.75 on the last candle.
+        assert_eq!(
+            run(IchimokuMode::TkCross, 2, false, &closes)[4..],
+            [Advice::Long, Advice::Short]
+        );
+        // The chikou span confirms the long as 12 is above the close of 10 two candles ago. It
+        // does not confirm the short as 11.5 is above it too.
+        assert_eq!(
+            run(IchimokuMode::TkCross, 2, true, &closes)[4..],
+            [Advice::Long, Advice::Liquidate]
+        );
+    }
+}
@@ -3,6 +3,7 @@ use crate::{genetics::Chromosome, indicators, Advice, Candle};
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct MacdParams {
@@ -31,7 +32,7 @@ fn signal_period(rng: &mut StdRng) -> u32 {
     rng.gen_range(1..100)
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct Macd {
     macd: indicators::Macd,
     advice: Advice,
@@ -67,4 +68,12 @@ impl Strategy for Macd {
             }
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("macd".into(), self.macd.value),
+            ("signal".into(), self.macd.signal),
+            ("histogram".into(), self.macd.histogram),
+        ]
+    }
 }
@@ -1,30 +1,41 @@
+mod bollinger;
 mod double_ma;
 mod double_ma_2;
 mod double_ma_stoch;
 mod four_week_rule;
+mod ichimoku;
 mod macd;
+mod osc_signal;
 mod rsi;
 mod sig;
 mod sig_osc;
 mod single_ma;
 mod stoch;
 mod triple_ma;
+mod turtle;
 
+pub use bollinger::{Bollinger, BollingerMode, BollingerParams, BollingerParamsContext};
 pub use double_ma::{DoubleMA, DoubleMAParams, DoubleMAParamsContext};
 pub use double_ma_2::{DoubleMA2, DoubleMA2Params, DoubleMA2ParamsContext};
 pub use double_ma_stoch::{DoubleMAStoch, DoubleMAStochParams, DoubleMAStochParamsContext};
 pub use four_week_rule::{FourWeekRule, FourWeekRuleParams, FourWeekRuleParamsContext};
+pub use ichimoku::{Ichimoku, IchimokuMode, IchimokuParams, IchimokuParamsContext};
 pub use macd::{Macd, MacdParams, MacdParamsContext};
-pub use rsi::{Rsi, RsiParams, RsiParamsContext};
+pub use osc_signal::{OscEntry, OscExit};
+pub use rsi::{Rsi, RsiParams, RsiParamsContext, RsiSignalParams, RsiSignalParamsContext};
 pub use sig::{Sig, SigParams, SigParamsContext};
 pub use sig_osc::{SigOsc, SigOscParams, SigOscParamsContext};
 pub use single_ma::{SingleMA, SingleMAParams, SingleMAParamsContext};
-pub use stoch::{Stoch, StochParams, StochParamsContext};
+pub use stoch::{
+    Stoch, StochParams, StochParamsContext, StochSignalParams, StochSignalParamsContext,
+};
 pub use triple_ma::{TripleMA, TripleMAParams, TripleMAParamsContext};
+pub use turtle::{Turtle, TurtleParams, TurtleParamsContext};
 
-use crate::{genetics::Chromosome, Advice, Candle, Interval};
+use crate::{genetics::Chromosome, utils::box_clone, Advice, Candle, Interval};
 use juno_derive::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 pub struct StrategyMeta {
     pub interval: Interval,
@@ -34,27 +45,51 @@ pub trait Strategy: Send + Sync {
     fn maturity(&self) -> u32;
     fn mature(&self) -> bool;
     fn update(&mut self, candle: &Candle);
+
+    // Named internal values, such as indicator readings, behind the latest advice. Only meant for
+    // debugging.
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        Vec::new()
+    }
+}
+
+// Qualifies the debug values of a component of a composite strategy, such as `sig.short_ma`, so that
+// they do not collide with those of its other components.
+fn prefixed(prefix: &str, values: Vec<(Cow<'static, str>, f64)>) -> Vec<(Cow<'static, str>, f64)> {
+    values
+        .into_iter()
+        .map(|(name, value)| (format!("{prefix}.{name}").into(), value))
+        .collect()
 }
 
-pub trait Signal: Strategy {
+pub trait Signal: Strategy + SignalClone {
     fn advice(&self) -> Advice;
 }
 
-pub trait Oscillator: Strategy {
+box_clone!(Signal, SignalClone);
+
+pub trait Oscillator: Strategy + OscillatorClone {
     fn overbought(&self) -> bool;
     fn oversold(&self) -> bool;
 }
 
+box_clone!(Oscillator, OscillatorClone);
+
 #[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum StrategyParams {
+    Bollinger(BollingerParams),
     DoubleMA(DoubleMAParams),
     DoubleMA2(DoubleMA2Params),
     DoubleMAStoch(DoubleMAStochParams),
     FourWeekRule(FourWeekRuleParams),
+    Ichimoku(IchimokuParams),
     Macd(MacdParams),
+    RsiSignal(RsiSignalParams),
     SingleMA(SingleMAParams),
+    StochSignal(StochSignalParams),
     TripleMA(TripleMAParams),
+    Turtle(TurtleParams),
     SigOsc(SigOscParams),
     Sig(SigParams),
 }
@@ -62,13 +97,18 @@ pub enum StrategyParams {
 impl StrategyParams {
     pub fn construct(&self, meta: &StrategyMeta) -> Box<dyn Signal> {
         match self {
+            Self::Bollinger(params) => Box::new(Bollinger::new(params, meta)),
             Self::DoubleMA(params) => Box::new(DoubleMA::new(params, meta)),
             Self::DoubleMA2(params) => Box::new(DoubleMA2::new(params, meta)),
             Self::DoubleMAStoch(params) => Box::new(DoubleMAStoch::new(params, meta)),
             Self::FourWeekRule(params) => Box::new(FourWeekRule::new(params, meta)),
+            Self::Ichimoku(params) => Box::new(Ichimoku::new(params, meta)),
             Self::Macd(params) => Box::new(Macd::new(params, meta)),
+            Self::RsiSignal(params) => Box::new(Rsi::with_signal(params, meta)),
             Self::SingleMA(params) => Box::new(SingleMA::new(params, meta)),
+            Self::StochSignal(params) => Box::new(Stoch::with_signal(params, meta)),
             Self::TripleMA(params) => Box::new(TripleMA::new(params, meta)),
+            Self::Turtle(params) => Box::new(Turtle::new(params, meta)),
             Self::SigOsc(params) => Box::new(SigOsc::new(params, meta)),
             Self::Sig(params) => Box::new(Sig::new(params, meta)),
         }
@@ -78,25 +118,35 @@ impl StrategyParams {
 #[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum SignalParams {
+    Bollinger(BollingerParams),
     DoubleMA(DoubleMAParams),
     DoubleMA2(DoubleMA2Params),
     DoubleMAStoch(DoubleMAStochParams),
     FourWeekRule(FourWeekRuleParams),
+    Ichimoku(IchimokuParams),
     Macd(MacdParams),
+    RsiSignal(RsiSignalParams),
     SingleMA(SingleMAParams),
+    StochSignal(StochSignalParams),
     TripleMA(TripleMAParams),
+    Turtle(TurtleParams),
 }
 
 impl SignalParams {
     pub fn construct(&self, meta: &StrategyMeta) -> Box<dyn Signal> {
         match self {
+            Self::Bollinger(params) => Box::new(Bollinger::new(params, meta)),
             Self::DoubleMA(params) => Box::new(DoubleMA::new(params, meta)),
             Self::DoubleMA2(params) => Box::new(DoubleMA2::new(params, meta)),
             Self::DoubleMAStoch(params) => Box::new(DoubleMAStoch::new(params, meta)),
             Self::FourWeekRule(params) => Box::new(FourWeekRule::new(params, meta)),
+            Self::Ichimoku(params) => Box::new(Ichimoku::new(params, meta)),
             Self::Macd(params) => Box::new(Macd::new(params, meta)),
+            Self::RsiSignal(params) => Box::new(Rsi::with_signal(params, meta)),
             Self::SingleMA(params) => Box::new(SingleMA::new(params, meta)),
+            Self::StochSignal(params) => Box::new(Stoch::with_signal(params, meta)),
             Self::TripleMA(params) => Box::new(TripleMA::new(params, meta)),
+            Self::Turtle(params) => Box::new(Turtle::new(params, meta)),
         }
     }
 }
@@ -0,0 +1,172 @@
+use crate::Advice;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const CENTERLINE: f64 = 50.0;
+
+// When an oscillator used as a signal enters a position.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum OscEntry {
+    // Long on crossing above the up threshold, short on crossing below the down threshold.
+    #[default]
+    ThresholdCross,
+    // Long on crossing back above the down threshold, short on crossing back below the up
+    // threshold.
+    MeanReversion,
+}
+
+// When an oscillator used as a signal exits a position, other than by reversing it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum OscExit {
+    // Held until the opposite entry.
+    #[default]
+    Opposite,
+    // Liquidated as soon as the oscillator crosses the centerline.
+    Centerline,
+}
+
+pub(super) fn entry(rng: &mut StdRng) -> OscEntry {
+    if rng.gen_bool(0.5) {
+        OscEntry::ThresholdCross
+    } else {
+        OscEntry::MeanReversion
+    }
+}
+pub(super) fn exit(rng: &mut StdRng) -> OscExit {
+    if rng.gen_bool(0.5) {
+        OscExit::Opposite
+    } else {
+        OscExit::Centerline
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct OscSignal {
+    entry: OscEntry,
+    exit: OscExit,
+    previous: Option<f64>,
+}
+
+impl OscSignal {
+    pub fn new(entry: OscEntry, exit: OscExit) -> Self {
+        Self {
+            entry,
+            exit,
+            previous: None,
+        }
+    }
+
+    // Advice following `advice` once the mature oscillator reads `value`.
+    pub fn update(&mut self, advice: Advice, value: f64, up: f64, down: f64) -> Advice {
+        let previous = match self.previous.replace(value) {
+            Some(previous) => previous,
+            None => return advice,
+        };
+        let crossed_above = |level| previous < level && value >= level;
+        let crossed_below = |level| previous >= level && value < level;
+
+        let (long, short) = match self.entry {
+            OscEntry::ThresholdCross => (crossed_above(up), crossed_below(down)),
+            OscEntry::MeanReversion => (crossed_above(down), crossed_below(up)),
+        };
+        if long {
+            Advice::Long
+        } else if short {
+            Advice::Short
+        } else if self.exit == OscExit::Centerline
+            && (advice == Advice::Long || advice == Advice::Short)
+            && (crossed_above(CENTERLINE) || crossed_below(CENTERLINE))
+        {
+            Advice::Liquidate
+        } else {
+            advice
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(signal: &mut OscSignal, values: &[f64]) -> Vec<Advice> {
+        let mut advice = Advice::None;
+        values
+            .iter()
+            .map(|&value| {
+                advice = signal.update(advice, value, 70.0, 30.0);
+                advice
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_threshold_cross_holds_until_opposite() {
+        let mut signal = OscSignal::new(OscEntry::ThresholdCross, OscExit::Opposite);
+        assert_eq!(
+            run(&mut signal, &[60.0, 75.0, 45.0, 25.0, 55.0, 75.0]),
+            [
+                Advice::None,
+                Advice::Long,
+                Advice::Long,
+                Advice::Short,
+                Advice::Short,
+                Advice::Long,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_threshold_cross_exits_on_centerline() {
+        let mut signal = OscSignal::new(OscEntry::ThresholdCross, OscExit::Centerline);
+        // Crossing the centerline while flat keeps it flat.
+        assert_eq!(
+            run(&mut signal, &[60.0, 75.0, 45.0, 55.0, 25.0, 55.0]),
+            [
+                Advice::None,
+                Advice::Long,
+                Advice::Liquidate,
+                Advice::Liquidate,
+                Advice::Short,
+                Advice::Liquidate,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mean_reversion_holds_until_opposite() {
+        let mut signal = OscSignal::new(OscEntry::MeanReversion, OscExit::Opposite);
+        assert_eq!(
+            run(&mut signal, &[40.0, 20.0, 35.0, 55.0, 80.0, 65.0, 45.0]),
+            [
+                Advice::None,
+                Advice::None,
+                Advice::Long,
+                Advice::Long,
+                Advice::Long,
+                Advice::Short,
+                Advice::Short,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mean_reversion_exits_on_centerline() {
+        let mut signal = OscSignal::new(OscEntry::MeanReversion, OscExit::Centerline);
+        assert_eq!(
+            run(
+                &mut signal,
+                &[40.0, 20.0, 35.0, 45.0, 55.0, 75.0, 65.0, 45.0]
+            ),
+            [
+                Advice::None,
+                Advice::None,
+                Advice::Long,
+                Advice::Long,
+                Advice::Liquidate,
+                Advice::Liquidate,
+                Advice::Short,
+                Advice::Liquidate,
+            ]
+        );
+    }
+}
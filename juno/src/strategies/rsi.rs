@@ -1,8 +1,12 @@
-use super::{Oscillator, Strategy, StrategyMeta};
-use crate::{genetics::Chromosome, indicators, Candle};
+use super::{
+    osc_signal::{entry, exit, OscSignal},
+    OscEntry, OscExit, Oscillator, Signal, Strategy, StrategyMeta,
+};
+use crate::{genetics::Chromosome, indicators, Advice, Candle};
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct RsiParams {
@@ -21,10 +25,24 @@ fn down_threshold(rng: &mut StdRng) -> f64 {
     rng.gen_range(0.0..50.0)
 }
 
+// The RSI traded as a signal on its own.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct RsiSignalParams {
+    #[chromosome]
+    pub rsi: RsiParams,
+    #[serde(default)]
+    pub entry: OscEntry,
+    #[serde(default)]
+    pub exit: OscExit,
+}
+
+#[derive(Clone, Signal)]
 pub struct Rsi {
     indicator: indicators::Rsi,
     up_threshold: f64,
     down_threshold: f64,
+    signal: OscSignal,
+    advice: Advice,
 }
 
 impl Rsi {
@@ -33,6 +51,15 @@ impl Rsi {
             indicator: indicators::Rsi::new(params.period),
             up_threshold: params.up_threshold,
             down_threshold: params.down_threshold,
+            signal: OscSignal::new(OscEntry::default(), OscExit::default()),
+            advice: Advice::None,
+        }
+    }
+
+    pub fn with_signal(params: &RsiSignalParams, meta: &StrategyMeta) -> Self {
+        Self {
+            signal: OscSignal::new(params.entry, params.exit),
+            ..Self::new(&params.rsi, meta)
         }
     }
 }
@@ -48,6 +75,19 @@ impl Strategy for Rsi {
 
     fn update(&mut self, candle: &Candle) {
         self.indicator.update(candle.close);
+
+        if self.indicator.mature() {
+            self.advice = self.signal.update(
+                self.advice,
+                self.indicator.value,
+                self.up_threshold,
+                self.down_threshold,
+            );
+        }
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![("rsi".into(), self.indicator.value)]
     }
 }
 
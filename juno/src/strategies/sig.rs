@@ -7,7 +7,10 @@ use crate::{
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::{max, min};
+use std::{
+    borrow::Cow,
+    cmp::{max, min},
+};
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct SigParams {
@@ -30,7 +33,7 @@ fn buffer_interval(_rng: &mut StdRng) -> Option<Interval> {
     None
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct Sig {
     sig: Box<dyn Signal>,
     mid_trend: MidTrend,
@@ -80,4 +83,8 @@ impl Strategy for Sig {
             }
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        self.sig.debug_values()
+    }
 }
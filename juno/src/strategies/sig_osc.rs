@@ -1,5 +1,5 @@
 use super::{
-    Oscillator, OscillatorParams, OscillatorParamsContext, Signal, SignalParams,
+    prefixed, Oscillator, OscillatorParams, OscillatorParamsContext, Signal, SignalParams,
     SignalParamsContext, Strategy, StrategyMeta,
 };
 use crate::{
@@ -10,7 +10,10 @@ use crate::{
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::{max, min};
+use std::{
+    borrow::Cow,
+    cmp::{max, min},
+};
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum OscFilter {
@@ -43,7 +46,7 @@ fn osc_filter(rng: &mut StdRng) -> OscFilter {
     }
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct SigOsc {
     sig: Box<dyn Signal>,
     osc: Box<dyn Oscillator>,
@@ -148,4 +151,10 @@ impl Strategy for SigOsc {
             );
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        let mut values = prefixed("sig", self.sig.debug_values());
+        values.extend(prefixed("osc", self.osc.debug_values()));
+        values
+    }
 }
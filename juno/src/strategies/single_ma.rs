@@ -1,4 +1,4 @@
-use super::{Signal, Strategy, StrategyMeta};
+use super::{prefixed, Signal, Strategy, StrategyMeta};
 use crate::{
     genetics::Chromosome,
     indicators::{MAExt, MAParams, MA},
@@ -7,7 +7,7 @@ use crate::{
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::min;
+use std::{borrow::Cow, cmp::min};
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct SingleMAParams {
@@ -19,7 +19,7 @@ fn ma(rng: &mut StdRng) -> MAParams {
     rng.gen_ma_params(period)
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct SingleMA {
     ma: Box<dyn MA>,
     previous_ma_value: f64,
@@ -70,4 +70,10 @@ impl Strategy for SingleMA {
             self.previous_ma_value = self.ma.value();
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        let mut values = prefixed("ma", self.ma.debug_values());
+        values.push(("previous_ma".into(), self.previous_ma_value));
+        values
+    }
 }
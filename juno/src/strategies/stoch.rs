@@ -1,8 +1,12 @@
-use super::{Oscillator, Strategy, StrategyMeta};
-use crate::{genetics::Chromosome, indicators, Candle};
+use super::{
+    osc_signal::{entry, exit, OscSignal},
+    OscEntry, OscExit, Oscillator, Signal, Strategy, StrategyMeta,
+};
+use crate::{genetics::Chromosome, indicators, Advice, Candle};
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct StochParams {
@@ -29,10 +33,24 @@ fn down_threshold(rng: &mut StdRng) -> f64 {
     rng.gen_range(0.0..50.0)
 }
 
+// The stochastic traded as a signal on its own.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct StochSignalParams {
+    #[chromosome]
+    pub stoch: StochParams,
+    #[serde(default)]
+    pub entry: OscEntry,
+    #[serde(default)]
+    pub exit: OscExit,
+}
+
+#[derive(Clone, Signal)]
 pub struct Stoch {
     pub indicator: indicators::Stoch,
     up_threshold: f64,
     down_threshold: f64,
+    signal: OscSignal,
+    advice: Advice,
 }
 
 impl Stoch {
@@ -45,6 +63,15 @@ impl Stoch {
             ),
             up_threshold: params.up_threshold,
             down_threshold: params.down_threshold,
+            signal: OscSignal::new(OscEntry::default(), OscExit::default()),
+            advice: Advice::None,
+        }
+    }
+
+    pub fn with_signal(params: &StochSignalParams, meta: &StrategyMeta) -> Self {
+        Self {
+            signal: OscSignal::new(params.entry, params.exit),
+            ..Self::new(&params.stoch, meta)
         }
     }
 }
@@ -60,6 +87,22 @@ impl Strategy for Stoch {
 
     fn update(&mut self, candle: &Candle) {
         self.indicator.update(candle.high, candle.low, candle.close);
+
+        if self.indicator.mature() {
+            self.advice = self.signal.update(
+                self.advice,
+                self.indicator.k,
+                self.up_threshold,
+                self.down_threshold,
+            );
+        }
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("k".into(), self.indicator.k),
+            ("d".into(), self.indicator.d),
+        ]
     }
 }
 
@@ -1,4 +1,4 @@
-use super::{Signal, Strategy, StrategyMeta};
+use super::{prefixed, Signal, Strategy, StrategyMeta};
 use crate::{
     genetics::Chromosome,
     indicators::{MAExt, MAParams, MA},
@@ -7,7 +7,7 @@ use crate::{
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cmp::max;
+use std::{borrow::Cow, cmp::max};
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct TripleMAParams {
@@ -31,7 +31,7 @@ fn mas(rng: &mut StdRng) -> (MAParams, MAParams, MAParams) {
     }
 }
 
-#[derive(Signal)]
+#[derive(Clone, Signal)]
 pub struct TripleMA {
     short_ma: Box<dyn MA>,
     medium_ma: Box<dyn MA>,
@@ -97,4 +97,11 @@ impl Strategy for TripleMA {
             }
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        let mut values = prefixed("short_ma", self.short_ma.debug_values());
+        values.extend(prefixed("medium_ma", self.medium_ma.debug_values()));
+        values.extend(prefixed("long_ma", self.long_ma.debug_values()));
+        values
+    }
 }
@@ -0,0 +1,184 @@
+use super::{Signal, Strategy, StrategyMeta};
+use crate::{genetics::Chromosome, indicators::Donchian, Advice, Candle};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, cmp::min};
+
+// Donchian channel breakout. Long on a close above the highest high of the last entry period
+// candles, short on a close below the lowest low. Liquidated on a close past the opposite side of
+// the channel over the shorter exit period.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct TurtleParams {
+    // Entry and exit periods, drawn together so that the exit period is the shorter.
+    pub periods: (u32, u32),
+    // If set, a breakout is not traded if the previous one, traded or not, was a winner.
+    #[serde(default)]
+    pub skip_after_winner: bool,
+}
+
+impl Default for TurtleParams {
+    fn default() -> Self {
+        // System 1 of the original rules.
+        Self {
+            periods: (20, 10),
+            skip_after_winner: true,
+        }
+    }
+}
+
+fn periods(rng: &mut StdRng) -> (u32, u32) {
+    let entry_period = rng.gen_range(3..100);
+    (entry_period, rng.gen_range(2..entry_period))
+}
+fn skip_after_winner(rng: &mut StdRng) -> bool {
+    rng.gen_bool(0.5)
+}
+
+#[derive(Clone, Signal)]
+pub struct Turtle {
+    entry: Donchian,
+    exit: Donchian,
+    skip_after_winner: bool,
+    // Direction and close of the latest breakout, until it is exited.
+    breakout: Option<(Advice, f64)>,
+    last_breakout_won: bool,
+    advice: Advice,
+    t: u32,
+    t1: u32,
+}
+
+impl Turtle {
+    pub fn new(params: &TurtleParams, _meta: &StrategyMeta) -> Self {
+        let (entry_period, exit_period) = params.periods;
+        assert!(exit_period < entry_period);
+
+        Self {
+            entry: Donchian::new(entry_period),
+            exit: Donchian::new(exit_period),
+            skip_after_winner: params.skip_after_winner,
+            breakout: None,
+            last_breakout_won: false,
+            advice: Advice::None,
+            t: 0,
+            t1: entry_period + 1,
+        }
+    }
+}
+
+fn won(direction: Advice, entry: f64, exit: f64) -> bool {
+    match direction {
+        Advice::Long => exit > entry,
+        _ => exit < entry,
+    }
+}
+
+impl Strategy for Turtle {
+    fn maturity(&self) -> u32 {
+        self.t1
+    }
+
+    fn mature(&self) -> bool {
+        self.t >= self.t1
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.t = min(self.t + 1, self.t1);
+
+        // Channels are of the candles before the current one.
+        if self.mature() {
+            let close = candle.close;
+
+            if let Some((direction, entry)) = self.breakout {
+                let exited = match direction {
+                    Advice::Long => close < self.exit.lower,
+                    _ => close > self.exit.upper,
+                };
+                if exited {
+                    self.last_breakout_won = won(direction, entry, close);
+                    self.breakout = None;
+                    if self.advice == direction {
+                        self.advice = Advice::Liquidate;
+                    }
+                }
+            }
+
+            let direction = if close > self.entry.upper {
+                Some(Advice::Long)
+            } else if close < self.entry.lower {
+                Some(Advice::Short)
+            } else {
+                None
+            };
+            if let Some(direction) = direction {
+                if let Some((previous, entry)) = self.breakout {
+                    if previous != direction {
+                        self.last_breakout_won = won(previous, entry, close);
+                    }
+                }
+                if self.breakout.map(|(previous, _)| previous) != Some(direction) {
+                    self.breakout = Some((direction, close));
+                    if !(self.skip_after_winner && self.last_breakout_won) {
+                        self.advice = direction;
+                    } else if matches!(self.advice, Advice::Long | Advice::Short) {
+                        // Skipped a reversal.
+                        self.advice = Advice::Liquidate;
+                    }
+                }
+            }
+        }
+
+        self.entry.update(candle.high, candle.low);
+        self.exit.update(candle.high, candle.low);
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("entry_upper".into(), self.entry.upper),
+            ("entry_lower".into(), self.entry.lower),
+            ("exit_upper".into(), self.exit.upper),
+            ("exit_lower".into(), self.exit.lower),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interval;
+
+    fn update(strategy: &mut Turtle, close: f64) -> Advice {
+        strategy.update(&Candle {
+            time: 0.into(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        });
+        strategy.advice()
+    }
+
+    #[test]
+    fn test_skips_breakout_after_winner() {
+        let mut strategy = Turtle::new(
+            &TurtleParams {
+                periods: (2, 1),
+                skip_after_winner: true,
+            },
+            &StrategyMeta {
+                interval: Interval::DAY_MS,
+            },
+        );
+        update(&mut strategy, 10.0);
+        update(&mut strategy, 10.0);
+        assert_eq!(update(&mut strategy, 11.0), Advice::Long);
+        assert_eq!(update(&mut strategy, 12.0), Advice::Long);
+        // Exited as a winner.
+        assert_eq!(update(&mut strategy, 11.5), Advice::Liquidate);
+        // The next breakout is skipped but tracked. It exits as a loser.
+        assert_eq!(update(&mut strategy, 13.0), Advice::Liquidate);
+        assert_eq!(update(&mut strategy, 12.5), Advice::Liquidate);
+        assert_eq!(update(&mut strategy, 14.0), Advice::Long);
+    }
+}
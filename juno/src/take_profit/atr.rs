@@ -0,0 +1,99 @@
+use super::TakeProfit;
+use crate::{
+    genetics::Chromosome,
+    indicators::{MAExt, MAParams},
+    utils::AtrBand,
+    Candle,
+};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Takes profit `multiplier` average true ranges, as of entry, away from the entry close. Inactive
+// for positions entered before the average true range has matured.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct AtrParams {
+    pub ma: MAParams,
+    pub multiplier: f64,
+}
+
+fn ma(rng: &mut StdRng) -> MAParams {
+    let period = rng.gen_range(2..100);
+    rng.gen_ma_params(period)
+}
+fn multiplier(rng: &mut StdRng) -> f64 {
+    rng.gen_range(0.5..10.0)
+}
+
+#[derive(Clone)]
+pub struct Atr {
+    band: AtrBand,
+    close: f64,
+}
+
+impl Atr {
+    pub fn new(params: &AtrParams) -> Self {
+        Self {
+            band: AtrBand::new(&params.ma, params.multiplier),
+            close: 0.0,
+        }
+    }
+}
+
+impl TakeProfit for Atr {
+    fn upside_hit(&self) -> bool {
+        matches!(self.upside_price(), Some(price) if self.close >= price)
+    }
+
+    fn downside_hit(&self) -> bool {
+        matches!(self.downside_price(), Some(price) if self.close <= price)
+    }
+
+    fn upside_price(&self) -> Option<f64> {
+        self.band.upper()
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        self.band.lower()
+    }
+
+    fn clear(&mut self, candle: &Candle) {
+        self.band.clear(candle);
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.close = candle.close;
+        self.band.update(candle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{indicators::SmaParams, utils::hlc_candle};
+
+    #[test]
+    fn test_sides_and_hits() {
+        let mut take_profit = Atr::new(&AtrParams {
+            ma: MAParams::Sma(SmaParams { period: 2 }),
+            multiplier: 2.0,
+        });
+        take_profit.update(&hlc_candle(11.0, 9.0, 10.0));
+        // Average true range of 2.5.
+        let entry = hlc_candle(13.0, 12.0, 12.5);
+        take_profit.update(&entry);
+        take_profit.clear(&entry);
+        assert_eq!(take_profit.upside_price(), Some(17.5));
+        assert_eq!(take_profit.downside_price(), Some(7.5));
+
+        take_profit.update(&hlc_candle(17.4, 17.4, 17.4));
+        assert!(!take_profit.upside_hit());
+        take_profit.update(&hlc_candle(17.5, 17.5, 17.5));
+        assert!(take_profit.upside_hit());
+
+        take_profit.update(&hlc_candle(7.6, 7.6, 7.6));
+        assert!(!take_profit.downside_hit());
+        take_profit.update(&hlc_candle(7.5, 7.5, 7.5));
+        assert!(take_profit.downside_hit());
+    }
+}
@@ -17,6 +17,7 @@ fn threshold(rng: &mut StdRng) -> f64 {
     }
 }
 
+#[derive(Clone)]
 pub struct Legacy {
     pub threshold: f64,
     close_at_position: f64,
@@ -42,6 +43,22 @@ impl TakeProfit for Legacy {
         self.threshold > 0.0 && self.close <= self.close_at_position * (1.0 - self.threshold)
     }
 
+    fn upside_price(&self) -> Option<f64> {
+        if self.threshold > 0.0 {
+            Some(self.close_at_position * (1.0 + self.threshold))
+        } else {
+            None
+        }
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        if self.threshold > 0.0 {
+            Some(self.close_at_position * (1.0 - self.threshold))
+        } else {
+            None
+        }
+    }
+
     fn clear(&mut self, candle: &Candle) {
         self.close_at_position = candle.close;
     }
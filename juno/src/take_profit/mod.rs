@@ -1,18 +1,21 @@
+mod atr;
 mod basic;
 mod legacy;
 mod noop;
 mod trending;
 
+pub use atr::{Atr, AtrParams, AtrParamsContext};
 pub use basic::{Basic, BasicParams, BasicParamsContext};
 pub use legacy::{Legacy, LegacyParams, LegacyParamsContext};
 pub use noop::{Noop, NoopParams, NoopParamsContext};
 pub use trending::{Trending, TrendingParams, TrendingParamsContext};
 
-use crate::{genetics::Chromosome, Candle};
+use crate::{genetics::Chromosome, utils::box_clone, Candle};
 use juno_derive::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
-pub trait TakeProfit: Send + Sync {
+pub trait TakeProfit: Send + Sync + TakeProfitClone {
     fn upside_hit(&self) -> bool {
         false
     }
@@ -21,14 +24,37 @@ pub trait TakeProfit: Send + Sync {
         false
     }
 
+    // Price at or above which profit is taken on a long position.
+    fn upside_price(&self) -> Option<f64> {
+        None
+    }
+
+    // Price at or below which profit is taken on a short position.
+    fn downside_price(&self) -> Option<f64> {
+        None
+    }
+
     fn clear(&mut self, _candle: &Candle) {}
 
     fn update(&mut self, _candle: &Candle) {}
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        [
+            ("upside_price", self.upside_price()),
+            ("downside_price", self.downside_price()),
+        ]
+        .into_iter()
+        .filter_map(|(name, price)| price.map(|price| (name.into(), price)))
+        .collect()
+    }
 }
 
+box_clone!(TakeProfit, TakeProfitClone);
+
 #[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum TakeProfitParams {
+    Atr(AtrParams),
     Basic(BasicParams),
     Legacy(LegacyParams),
     Noop(NoopParams),
@@ -38,6 +64,7 @@ pub enum TakeProfitParams {
 impl TakeProfitParams {
     pub fn construct(&self) -> Box<dyn TakeProfit> {
         match self {
+            Self::Atr(params) => Box::new(Atr::new(params)),
             Self::Basic(params) => Box::new(Basic::new(params)),
             Self::Legacy(params) => Box::new(Legacy::new(params)),
             Self::Noop(params) => Box::new(Noop::new(params)),
@@ -9,6 +9,7 @@ use crate::{
 use juno_derive::*;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct TrendingParams {
@@ -40,6 +41,7 @@ fn easing(rng: &mut StdRng) -> Easing {
     rng.gen_easing()
 }
 
+#[derive(Clone)]
 pub struct Trending {
     up_min_threshold: f64,
     up_max_threshold: f64,
@@ -90,6 +92,14 @@ impl TakeProfit for Trending {
         self.close <= self.close_at_position * self.down_threshold_factor
     }
 
+    fn upside_price(&self) -> Option<f64> {
+        Some(self.close_at_position * self.up_threshold_factor)
+    }
+
+    fn downside_price(&self) -> Option<f64> {
+        Some(self.close_at_position * self.down_threshold_factor)
+    }
+
     fn clear(&mut self, candle: &Candle) {
         self.close_at_position = candle.close;
         if self.lock_threshold {
@@ -104,4 +114,18 @@ impl TakeProfit for Trending {
             self.set_threshold_factors();
         }
     }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("adx".into(), self.adx.value),
+            (
+                "upside_price".into(),
+                self.close_at_position * self.up_threshold_factor,
+            ),
+            (
+                "downside_price".into(),
+                self.close_at_position * self.down_threshold_factor,
+            ),
+        ]
+    }
 }
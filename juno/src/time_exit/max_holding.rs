@@ -0,0 +1,53 @@
+use super::TimeExit;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+// Closes a position once it has been held for `candles` candles.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct MaxHoldingParams {
+    pub candles: u32,
+}
+
+fn candles(rng: &mut StdRng) -> u32 {
+    rng.gen_range(1..200)
+}
+
+#[derive(Clone)]
+pub struct MaxHolding {
+    candles: u32,
+    held: u32,
+}
+
+impl MaxHolding {
+    pub fn new(params: &MaxHoldingParams) -> Self {
+        Self {
+            candles: params.candles,
+            held: 0,
+        }
+    }
+}
+
+impl TimeExit for MaxHolding {
+    fn upside_hit(&self) -> bool {
+        self.held >= self.candles
+    }
+
+    fn downside_hit(&self) -> bool {
+        self.held >= self.candles
+    }
+
+    fn clear(&mut self, _candle: &Candle) {
+        self.held = 0;
+    }
+
+    fn update(&mut self, _candle: &Candle) {
+        self.held += 1;
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![("held".into(), self.held as f64)]
+    }
+}
@@ -0,0 +1,62 @@
+mod max_holding;
+mod no_new_high;
+mod noop;
+mod not_in_profit;
+
+pub use max_holding::{MaxHolding, MaxHoldingParams, MaxHoldingParamsContext};
+pub use no_new_high::{NoNewHigh, NoNewHighParams, NoNewHighParamsContext};
+pub use noop::{Noop, NoopParams, NoopParamsContext};
+pub use not_in_profit::{NotInProfit, NotInProfitParams, NotInProfitParamsContext};
+
+use crate::{genetics::Chromosome, utils::box_clone, Candle};
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+// Closes a position based on its age. Evaluated at candle close.
+pub trait TimeExit: Send + Sync + TimeExitClone {
+    fn upside_hit(&self) -> bool {
+        false
+    }
+
+    fn downside_hit(&self) -> bool {
+        false
+    }
+
+    // Called on the candle a position is entered on.
+    fn clear(&mut self, _candle: &Candle) {}
+
+    fn update(&mut self, _candle: &Candle) {}
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        Vec::new()
+    }
+}
+
+box_clone!(TimeExit, TimeExitClone);
+
+#[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum TimeExitParams {
+    MaxHolding(MaxHoldingParams),
+    NoNewHigh(NoNewHighParams),
+    Noop(NoopParams),
+    NotInProfit(NotInProfitParams),
+}
+
+impl Default for TimeExitParams {
+    fn default() -> Self {
+        Self::Noop(NoopParams {})
+    }
+}
+
+impl TimeExitParams {
+    pub fn construct(&self) -> Box<dyn TimeExit> {
+        match self {
+            Self::MaxHolding(params) => Box::new(MaxHolding::new(params)),
+            Self::NoNewHigh(params) => Box::new(NoNewHigh::new(params)),
+            Self::Noop(params) => Box::new(Noop::new(params)),
+            Self::NotInProfit(params) => Box::new(NotInProfit::new(params)),
+        }
+    }
+}
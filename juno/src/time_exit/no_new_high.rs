@@ -0,0 +1,77 @@
+use super::TimeExit;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+// Closes a long position if no close has exceeded the highest close since entry for `candles`
+// candles. Likewise for a short position and the lowest close.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct NoNewHighParams {
+    pub candles: u32,
+}
+
+fn candles(rng: &mut StdRng) -> u32 {
+    rng.gen_range(1..200)
+}
+
+#[derive(Clone)]
+pub struct NoNewHigh {
+    candles: u32,
+    highest_close_since_position: f64,
+    lowest_close_since_position: f64,
+    since_high: u32,
+    since_low: u32,
+}
+
+impl NoNewHigh {
+    pub fn new(params: &NoNewHighParams) -> Self {
+        Self {
+            candles: params.candles,
+            highest_close_since_position: 0.0,
+            lowest_close_since_position: f64::MAX,
+            since_high: 0,
+            since_low: 0,
+        }
+    }
+}
+
+impl TimeExit for NoNewHigh {
+    fn upside_hit(&self) -> bool {
+        self.since_high >= self.candles
+    }
+
+    fn downside_hit(&self) -> bool {
+        self.since_low >= self.candles
+    }
+
+    fn clear(&mut self, candle: &Candle) {
+        self.highest_close_since_position = candle.close;
+        self.lowest_close_since_position = candle.close;
+        self.since_high = 0;
+        self.since_low = 0;
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        if candle.close > self.highest_close_since_position {
+            self.highest_close_since_position = candle.close;
+            self.since_high = 0;
+        } else {
+            self.since_high += 1;
+        }
+        if candle.close < self.lowest_close_since_position {
+            self.lowest_close_since_position = candle.close;
+            self.since_low = 0;
+        } else {
+            self.since_low += 1;
+        }
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![
+            ("since_high".into(), self.since_high as f64),
+            ("since_low".into(), self.since_low as f64),
+        ]
+    }
+}
@@ -0,0 +1,18 @@
+use super::TimeExit;
+use crate::genetics::Chromosome;
+use juno_derive::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct NoopParams {}
+
+#[derive(Clone)]
+pub struct Noop {}
+
+impl Noop {
+    pub fn new(_params: &NoopParams) -> Self {
+        Self {}
+    }
+}
+
+impl TimeExit for Noop {}
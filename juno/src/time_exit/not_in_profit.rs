@@ -0,0 +1,60 @@
+use super::TimeExit;
+use crate::{genetics::Chromosome, Candle};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+// Closes a position which has been held for `candles` candles without the close moving past the
+// close it was entered on.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct NotInProfitParams {
+    pub candles: u32,
+}
+
+fn candles(rng: &mut StdRng) -> u32 {
+    rng.gen_range(1..200)
+}
+
+#[derive(Clone)]
+pub struct NotInProfit {
+    candles: u32,
+    held: u32,
+    close_at_position: f64,
+    close: f64,
+}
+
+impl NotInProfit {
+    pub fn new(params: &NotInProfitParams) -> Self {
+        Self {
+            candles: params.candles,
+            held: 0,
+            close_at_position: 0.0,
+            close: 0.0,
+        }
+    }
+}
+
+impl TimeExit for NotInProfit {
+    fn upside_hit(&self) -> bool {
+        self.held >= self.candles && self.close <= self.close_at_position
+    }
+
+    fn downside_hit(&self) -> bool {
+        self.held >= self.candles && self.close >= self.close_at_position
+    }
+
+    fn clear(&mut self, candle: &Candle) {
+        self.held = 0;
+        self.close_at_position = candle.close;
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.held += 1;
+        self.close = candle.close;
+    }
+
+    fn debug_values(&self) -> Vec<(Cow<'static, str>, f64)> {
+        vec![("held".into(), self.held as f64)]
+    }
+}
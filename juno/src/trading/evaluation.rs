@@ -1,10 +1,14 @@
-use super::{TradeInput, TradingParams};
+use super::{
+    trade_portfolio, Allocation, Blackout, EntryOrder, ExecutionTiming, ExitTrigger, GapPolicy,
+    Market, PortfolioInput, TradeInput, TradingParams,
+};
 use crate::{
     clients::juno_core,
     genetics::{Evaluation, Individual},
-    statistics,
+    slippage::SlippageParams,
+    statistics::{self, ExtendedStatistics},
     trading::trade,
-    BorrowInfo, Candle, ExchangeInfo, Fees, Filters, Interval, SymbolExt, Timestamp,
+    BorrowInfo, Candle, ExchangeInfo, Fees, Filters, FundingRate, Interval, SymbolExt, Timestamp,
 };
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -55,12 +59,23 @@ struct SymbolCtx {
     fees: Fees,
     filters: Filters,
     borrow_info: BorrowInfo,
+    funding_rates: Vec<FundingRate>,
 }
 
 pub struct BasicEvaluation {
     symbol_ctxs: Vec<SymbolCtx>,
     prices: HashMap<String, Vec<f64>>,
     quote: f64,
+    maintenance_margin_level: f64,
+    exit_trigger: ExitTrigger,
+    execution_timing: ExecutionTiming,
+    entry_order: EntryOrder,
+    slippage: SlippageParams,
+    market: Market,
+    blackouts: Vec<Blackout>,
+    gap_policy: GapPolicy,
+    penalize_halted: bool,
+    portfolio: Option<Allocation>,
     stats_interval: Interval,
     evaluation_statistic: EvaluationStatistic,
     evaluation_aggregation_fn: fn(f64, f64) -> f64,
@@ -75,6 +90,21 @@ pub struct BasicEvaluationInput<'a> {
     pub start: Timestamp,
     pub end: Timestamp,
     pub quote: f64,
+    pub maintenance_margin_level: f64,
+    pub exit_trigger: ExitTrigger,
+    pub execution_timing: ExecutionTiming,
+    pub entry_order: EntryOrder,
+    pub slippage: SlippageParams,
+    pub market: Market,
+    // Funding rates per symbol. Symbols without an entry pay no funding.
+    pub funding_rates: &'a HashMap<String, Vec<FundingRate>>,
+    pub blackouts: &'a [Blackout],
+    pub gap_policy: GapPolicy,
+    // Whether a symbol on which trading halted early scores negative infinity.
+    pub penalize_halted: bool,
+    // If set, all symbols are traded as a single portfolio from a shared quote and the account
+    // is evaluated instead of every symbol on its own.
+    pub portfolio: Option<Allocation>,
     pub evaluation_statistic: EvaluationStatistic,
     pub evaluation_aggregation: EvaluationAggregation,
 }
@@ -96,6 +126,7 @@ impl BasicEvaluation {
                     fees: input.exchange_info.fees[symbol],
                     filters: input.exchange_info.filters[symbol],
                     borrow_info: input.exchange_info.borrow_info[symbol][symbol.base_asset()],
+                    funding_rates: input.funding_rates.get(symbol).cloned().unwrap_or_default(),
                 }
             })
             .collect();
@@ -104,6 +135,16 @@ impl BasicEvaluation {
             symbol_ctxs,
             stats_interval,
             quote: input.quote,
+            maintenance_margin_level: input.maintenance_margin_level,
+            exit_trigger: input.exit_trigger,
+            execution_timing: input.execution_timing,
+            entry_order: input.entry_order,
+            slippage: input.slippage,
+            market: input.market,
+            blackouts: input.blackouts.to_vec(),
+            gap_policy: input.gap_policy,
+            penalize_halted: input.penalize_halted,
+            portfolio: input.portfolio,
             evaluation_statistic: input.evaluation_statistic,
             evaluation_aggregation_fn: match input.evaluation_aggregation {
                 EvaluationAggregation::Linear => sum_linear,
@@ -114,20 +155,35 @@ impl BasicEvaluation {
         }
     }
 
+    fn trade_input<'a>(
+        &'a self,
+        symbol_ctx: &'a SymbolCtx,
+        chromosome: &TradingParams,
+    ) -> TradeInput<'a> {
+        TradeInput {
+            candles: &symbol_ctx.interval_candles[&chromosome.trader.interval],
+            fees: &symbol_ctx.fees,
+            filters: &symbol_ctx.filters,
+            borrow_info: &symbol_ctx.borrow_info,
+            maintenance_margin_level: self.maintenance_margin_level,
+            quote: self.quote,
+            exit_trigger: self.exit_trigger,
+            execution_timing: self.execution_timing,
+            entry_order: self.entry_order,
+            slippage: self.slippage,
+            market: self.market,
+            funding_rates: &symbol_ctx.funding_rates,
+            blackouts: &self.blackouts,
+            gap_policy: self.gap_policy,
+            record: false,
+        }
+    }
+
     fn evaluate_symbol(&self, symbol_ctx: &SymbolCtx, chromosome: &TradingParams) -> f64 {
-        let summary = trade(
-            chromosome,
-            &TradeInput {
-                candles: &symbol_ctx.interval_candles[&chromosome.trader.interval],
-                fees: &symbol_ctx.fees,
-                filters: &symbol_ctx.filters,
-                borrow_info: &symbol_ctx.borrow_info,
-                margin_multiplier: 2,
-                quote: self.quote,
-                long: true,
-                short: true,
-            },
-        );
+        let summary = trade(chromosome, &self.trade_input(symbol_ctx, chromosome));
+        if self.penalize_halted && summary.halted.is_some() {
+            return f64::NEG_INFINITY;
+        }
         match self.evaluation_statistic {
             EvaluationStatistic::Profit => statistics::get_profit(&summary),
             EvaluationStatistic::ReturnOverMaxDrawdown => {
@@ -147,6 +203,45 @@ impl BasicEvaluation {
             ),
         }
     }
+
+    fn evaluate_portfolio(&self, allocation: Allocation, chromosome: &TradingParams) -> f64 {
+        let symbols: Vec<_> = self
+            .symbol_ctxs
+            .iter()
+            .map(|ctx| ctx.symbol.clone())
+            .collect();
+        let inputs: Vec<_> = self
+            .symbol_ctxs
+            .iter()
+            .map(|ctx| self.trade_input(ctx, chromosome))
+            .collect();
+        let summary = trade_portfolio(
+            chromosome,
+            &PortfolioInput {
+                symbols: &symbols,
+                inputs: &inputs,
+                quote: self.quote,
+                allocation,
+            },
+        );
+        if self.penalize_halted && summary.account.halted.is_some() {
+            return f64::NEG_INFINITY;
+        }
+        match self.evaluation_statistic {
+            EvaluationStatistic::Profit => statistics::get_profit(&summary.account),
+            EvaluationStatistic::ReturnOverMaxDrawdown => {
+                statistics::get_return_over_max_drawdown(&summary.account)
+            }
+            EvaluationStatistic::SharpeRatio => {
+                ExtendedStatistics::compose_equity(self.quote, &summary.equity, self.stats_interval)
+                    .sharpe_ratio
+            }
+            EvaluationStatistic::SortinoRatio => {
+                ExtendedStatistics::compose_equity(self.quote, &summary.equity, self.stats_interval)
+                    .sortino_ratio
+            }
+        }
+    }
 }
 
 impl Evaluation for BasicEvaluation {
@@ -161,11 +256,17 @@ impl Evaluation for BasicEvaluation {
             // .iter_mut()
             .par_iter_mut()
             .for_each(|ind| {
-                ind.fitness = self
-                    .symbol_ctxs
-                    .iter()
-                    .map(|ctx| self.evaluate_symbol(ctx, &ind.chromosome))
-                    .fold(0.0, self.evaluation_aggregation_fn)
+                ind.fitness = match self.portfolio {
+                    Some(allocation) => (self.evaluation_aggregation_fn)(
+                        0.0,
+                        self.evaluate_portfolio(allocation, &ind.chromosome),
+                    ),
+                    None => self
+                        .symbol_ctxs
+                        .iter()
+                        .map(|ctx| self.evaluate_symbol(ctx, &ind.chromosome))
+                        .fold(0.0, self.evaluation_aggregation_fn),
+                }
             });
     }
 }
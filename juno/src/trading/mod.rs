@@ -1,20 +1,30 @@
 mod evaluation;
+mod portfolio;
+mod re_entry;
+mod session;
 mod traders;
 
 pub use evaluation::*;
+pub use portfolio::*;
+pub use re_entry::*;
+pub use session::*;
 pub use traders::*;
 
 use crate::{
+    candle_transform::{CandleTransformParams, CandleTransformParamsContext},
     genetics::Chromosome,
+    position_sizer::{PositionSizerParams, PositionSizerParamsContext},
     stop_loss::{StopLossParams, StopLossParamsContext},
     strategies::{StrategyParams, StrategyParamsContext},
     take_profit::{TakeProfitParams, TakeProfitParamsContext},
-    Fill, Interval, Timestamp,
+    time_exit::{TimeExitParams, TimeExitParamsContext},
+    Advice, Fill, Interval, Timestamp,
 };
 use juno_derive::*;
 use rand::prelude::*;
-use serde::{Deserialize, Serialize};
-use std::mem;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::{borrow::Cow, collections::HashMap, mem};
+use thiserror::Error;
 
 #[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct TradingParams {
@@ -26,52 +36,220 @@ pub struct TradingParams {
     pub stop_loss: StopLossParams,
     #[chromosome]
     pub take_profit: TakeProfitParams,
+    #[chromosome]
+    #[serde(default)]
+    pub time_exit: TimeExitParams,
+    #[chromosome]
+    #[serde(default)]
+    pub position_sizer: PositionSizerParams,
+    // Candles the strategy is fed.
+    #[chromosome]
+    #[serde(default)]
+    pub candle_transform: CandleTransformParams,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct TraderParams {
     pub interval: Interval,
+    #[serde(default)]
+    pub direction: Direction,
+    #[serde(
+        default = "default_margin_multiplier",
+        deserialize_with = "deserialize_margin_multiplier"
+    )]
+    pub margin_multiplier: u32,
+    #[serde(default)]
+    pub re_entry: ReEntryParams,
+    #[serde(default)]
+    pub session: SessionFilterParams,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+// Which sides of the market positions may be opened on.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Direction {
+    Long,
+    Short,
+    #[default]
+    Both,
+}
+
+impl Direction {
+    pub fn long(&self) -> bool {
+        *self != Self::Short
+    }
+
+    pub fn short(&self) -> bool {
+        *self != Self::Long
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(try_from = "UncheckedTraderParamsContext")]
 pub struct TraderParamsContext {
     pub intervals: Vec<Interval>,
+    #[serde(default = "default_directions")]
+    pub directions: Vec<Direction>,
+    // Inclusive bounds of the margin multiplier. Equal bounds pin it.
+    #[serde(default = "default_margin_multiplier")]
+    pub min_margin_multiplier: u32,
+    #[serde(default = "default_margin_multiplier")]
+    pub max_margin_multiplier: u32,
+    // Pinned to waiting for a fresh signal unless set.
+    #[serde(default = "default_re_entry")]
+    pub re_entry: ReEntryParamsContext,
+    // Unrestricted unless set.
+    #[serde(default = "SessionFilterParamsContext::unrestricted")]
+    pub session: SessionFilterParamsContext,
+}
+
+impl Default for TraderParamsContext {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+            directions: default_directions(),
+            min_margin_multiplier: default_margin_multiplier(),
+            max_margin_multiplier: default_margin_multiplier(),
+            re_entry: default_re_entry(),
+            session: SessionFilterParamsContext::unrestricted(),
+        }
+    }
+}
+
+// Mirrors `TraderParamsContext` so that it can be validated once deserialized.
+#[derive(Deserialize)]
+struct UncheckedTraderParamsContext {
+    intervals: Vec<Interval>,
+    #[serde(default = "default_directions")]
+    directions: Vec<Direction>,
+    #[serde(default = "default_margin_multiplier")]
+    min_margin_multiplier: u32,
+    #[serde(default = "default_margin_multiplier")]
+    max_margin_multiplier: u32,
+    #[serde(default = "default_re_entry")]
+    re_entry: ReEntryParamsContext,
+    #[serde(default = "SessionFilterParamsContext::unrestricted")]
+    session: SessionFilterParamsContext,
+}
+
+impl TryFrom<UncheckedTraderParamsContext> for TraderParamsContext {
+    type Error = MarginMultiplierError;
+
+    fn try_from(ctx: UncheckedTraderParamsContext) -> Result<Self, Self::Error> {
+        validate_margin_multiplier(ctx.min_margin_multiplier)?;
+        if ctx.min_margin_multiplier > ctx.max_margin_multiplier {
+            return Err(MarginMultiplierError::InvalidBounds {
+                min: ctx.min_margin_multiplier,
+                max: ctx.max_margin_multiplier,
+            });
+        }
+        Ok(Self {
+            intervals: ctx.intervals,
+            directions: ctx.directions,
+            min_margin_multiplier: ctx.min_margin_multiplier,
+            max_margin_multiplier: ctx.max_margin_multiplier,
+            re_entry: ctx.re_entry,
+            session: ctx.session,
+        })
+    }
+}
+
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum MarginMultiplierError {
+    #[error("margin multiplier {0} leaves nothing to borrow for a short position")]
+    TooLow(u32),
+    #[error("min margin multiplier {min} is above max margin multiplier {max}")]
+    InvalidBounds { min: u32, max: u32 },
+}
+
+// A multiplier of 1 would leave nothing to borrow for a short position.
+fn validate_margin_multiplier(margin_multiplier: u32) -> Result<(), MarginMultiplierError> {
+    if margin_multiplier < 2 {
+        return Err(MarginMultiplierError::TooLow(margin_multiplier));
+    }
+    Ok(())
+}
+
+fn deserialize_margin_multiplier<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    let margin_multiplier = u32::deserialize(deserializer)?;
+    validate_margin_multiplier(margin_multiplier).map_err(de::Error::custom)?;
+    Ok(margin_multiplier)
+}
+
+fn default_directions() -> Vec<Direction> {
+    vec![Direction::Both]
+}
+
+fn default_margin_multiplier() -> u32 {
+    2
+}
+
+fn default_re_entry() -> ReEntryParamsContext {
+    ReEntryParamsContext::FreshSignal(FreshSignalParamsContext {})
+}
+
+impl TraderParamsContext {
+    fn interval(&self, rng: &mut StdRng) -> Interval {
+        match self.intervals.len() {
+            0 => panic!(),
+            1 => self.intervals[0],
+            _ => *self.intervals.choose(rng).unwrap(),
+        }
+    }
+
+    fn direction(&self, rng: &mut StdRng) -> Direction {
+        match self.directions.len() {
+            0 => panic!(),
+            1 => self.directions[0],
+            _ => *self.directions.choose(rng).unwrap(),
+        }
+    }
+
+    // Bounds are validated when deserialized.
+    fn margin_multiplier(&self, rng: &mut StdRng) -> u32 {
+        rng.gen_range(self.min_margin_multiplier..=self.max_margin_multiplier)
+    }
 }
 
 impl Chromosome for TraderParams {
     type Context = TraderParamsContext;
 
     fn len() -> usize {
-        1
+        3 + ReEntryParams::len() + SessionFilterParams::len()
     }
 
     fn generate(rng: &mut StdRng, ctx: &Self::Context) -> Self {
         Self {
-            interval: match ctx.intervals.len() {
-                0 => panic!(),
-                1 => ctx.intervals[0],
-                _ => *ctx.intervals.choose(rng).unwrap(),
-            },
+            interval: ctx.interval(rng),
+            direction: ctx.direction(rng),
+            margin_multiplier: ctx.margin_multiplier(rng),
+            re_entry: ReEntryParams::generate(rng, &ctx.re_entry),
+            session: SessionFilterParams::generate(rng, &ctx.session),
         }
     }
 
     fn cross(&mut self, other: &mut Self, i: usize) {
         match i {
             0 => mem::swap(&mut self.interval, &mut other.interval),
-            _ => panic!(),
+            1 => mem::swap(&mut self.direction, &mut other.direction),
+            2 => mem::swap(&mut self.margin_multiplier, &mut other.margin_multiplier),
+            i if i < 3 + ReEntryParams::len() => self.re_entry.cross(&mut other.re_entry, i - 3),
+            i => self
+                .session
+                .cross(&mut other.session, i - 3 - ReEntryParams::len()),
         };
     }
 
     fn mutate(&mut self, rng: &mut StdRng, i: usize, ctx: &Self::Context) {
         match i {
-            0 => {
-                self.interval = match ctx.intervals.len() {
-                    0 => panic!(),
-                    1 => ctx.intervals[0],
-                    _ => *ctx.intervals.choose(rng).unwrap(),
-                }
-            }
-            _ => panic!(),
+            0 => self.interval = ctx.interval(rng),
+            1 => self.direction = ctx.direction(rng),
+            2 => self.margin_multiplier = ctx.margin_multiplier(rng),
+            i if i < 3 + ReEntryParams::len() => self.re_entry.mutate(rng, i - 3, &ctx.re_entry),
+            i => self
+                .session
+                .mutate(rng, i - 3 - ReEntryParams::len(), &ctx.session),
         };
     }
 }
@@ -82,26 +260,62 @@ pub enum CloseReason {
     StopLoss,
     TakeProfit,
     Cancelled,
+    Liquidation,
+    Blackout,
+    TimeExit,
 }
 
+#[derive(Clone)]
 pub enum OpenPosition {
     Long(OpenLongPosition),
     Short(OpenShortPosition),
+    Futures(OpenFuturesPosition),
+}
+
+impl OpenPosition {
+    // Either `Advice::Long` or `Advice::Short`.
+    pub fn advice(&self) -> Advice {
+        match self {
+            Self::Long(_) => Advice::Long,
+            Self::Short(_) => Advice::Short,
+            Self::Futures(pos) => pos.advice,
+        }
+    }
 }
 
+#[derive(Clone)]
 pub struct OpenLongPosition {
     pub time: Timestamp,
-    pub fills: [Fill; 1],
+    pub fills: Vec<Fill>,
+    // Fills of any partial exits made before the position is closed.
+    pub close_fills: Vec<Fill>,
 }
 
 impl OpenLongPosition {
-    pub fn close(self, time: Timestamp, fills: [Fill; 1], reason: CloseReason) -> LongPosition {
+    pub fn new(time: Timestamp, fills: Vec<Fill>) -> Self {
+        Self {
+            time,
+            fills,
+            close_fills: Vec::new(),
+        }
+    }
+
+    pub fn scale_in(&mut self, fills: Vec<Fill>) {
+        self.fills.extend(fills);
+    }
+
+    pub fn scale_out(&mut self, fills: Vec<Fill>) {
+        self.close_fills.extend(fills);
+    }
+
+    pub fn close(mut self, time: Timestamp, fills: Vec<Fill>, reason: CloseReason) -> LongPosition {
+        self.close_fills.extend(fills);
         LongPosition {
             open_time: self.time,
             open_fills: self.fills,
 
             close_time: time,
-            close_fills: fills,
+            close_fills: self.close_fills,
             close_reason: reason,
         }
     }
@@ -113,23 +327,144 @@ impl OpenLongPosition {
     pub fn base_gain(&self) -> f64 {
         Fill::total_size(&self.fills) - Fill::total_fee(&self.fills)
     }
+
+    // Base asset still held after partial exits.
+    pub fn base_balance(&self) -> f64 {
+        self.base_gain() - Fill::total_size(&self.close_fills)
+    }
 }
 
+#[derive(Clone)]
 pub struct OpenShortPosition {
     pub time: Timestamp,
     pub collateral: f64,
     pub borrowed: f64,
-    pub fills: [Fill; 1],
+    // Part of `borrowed` already paid back through partial exits.
+    pub repaid: f64,
+    pub fills: Vec<Fill>,
+    // Fills of any partial exits made before the position is closed.
+    pub close_fills: Vec<Fill>,
 }
 
 impl OpenShortPosition {
-    pub fn close(self, time: Timestamp, fills: [Fill; 1], reason: CloseReason) -> ShortPosition {
+    pub fn new(time: Timestamp, collateral: f64, borrowed: f64, fills: Vec<Fill>) -> Self {
+        Self {
+            time,
+            collateral,
+            borrowed,
+            repaid: 0.0,
+            fills,
+            close_fills: Vec::new(),
+        }
+    }
+
+    pub fn scale_in(&mut self, collateral: f64, borrowed: f64, fills: Vec<Fill>) {
+        self.collateral += collateral;
+        self.borrowed += borrowed;
+        self.fills.extend(fills);
+    }
+
+    pub fn scale_out(&mut self, repaid: f64, fills: Vec<Fill>) {
+        self.repaid += repaid;
+        self.close_fills.extend(fills);
+    }
+
+    pub fn close(
+        mut self,
+        time: Timestamp,
+        fills: Vec<Fill>,
+        reason: CloseReason,
+    ) -> ShortPosition {
+        self.close_fills.extend(fills);
         ShortPosition {
             open_time: self.time,
             collateral: self.collateral,
             borrowed: self.borrowed,
             open_fills: self.fills,
 
+            close_time: time,
+            close_fills: self.close_fills,
+            close_reason: reason,
+        }
+    }
+
+    // Quote held against the loan: collateral and sale proceeds less what partial exits spent.
+    pub fn assets(&self) -> f64 {
+        self.collateral + Fill::total_quote(&self.fills)
+            - Fill::total_fee(&self.fills)
+            - Fill::total_quote(&self.close_fills)
+    }
+
+    // Borrowed base asset not yet paid back.
+    pub fn outstanding(&self) -> f64 {
+        self.borrowed - self.repaid
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenFuturesPosition {
+    pub time: Timestamp,
+    // Either `Advice::Long` or `Advice::Short`.
+    pub advice: Advice,
+    // Isolated margin backing the position.
+    pub margin: f64,
+    // Net funding paid out of the margin. Negative if funding was received.
+    pub funding: f64,
+    pub fills: Vec<Fill>,
+}
+
+impl OpenFuturesPosition {
+    pub fn new(time: Timestamp, advice: Advice, margin: f64, fills: Vec<Fill>) -> Self {
+        Self {
+            time,
+            advice,
+            margin,
+            funding: 0.0,
+            fills,
+        }
+    }
+
+    pub fn size(&self) -> f64 {
+        Fill::total_size(&self.fills)
+    }
+
+    // Unrealized profit or loss if the position was closed at `price`, before fees.
+    pub fn pnl(&self, price: f64) -> f64 {
+        futures_pnl(
+            self.advice,
+            Fill::total_quote(&self.fills),
+            self.size() * price,
+        )
+    }
+
+    // Longs pay and shorts receive a positive funding rate.
+    pub fn pay_funding(&mut self, rate: f64, price: f64) {
+        let payment = rate * self.size() * price;
+        self.funding += match self.advice {
+            Advice::Long => payment,
+            _ => -payment,
+        };
+    }
+
+    // Price at which the remaining margin equals `maintenance_margin_rate` of the notional value.
+    pub fn liquidation_price(&self, maintenance_margin_rate: f64) -> f64 {
+        let size = self.size();
+        let margin = self.margin - self.funding;
+        let quote = Fill::total_quote(&self.fills);
+        match self.advice {
+            Advice::Long => (quote - margin) / (size * (1.0 - maintenance_margin_rate)),
+            _ => (quote + margin) / (size * (1.0 + maintenance_margin_rate)),
+        }
+    }
+
+    pub fn close(self, time: Timestamp, fills: Vec<Fill>, reason: CloseReason) -> FuturesPosition {
+        FuturesPosition {
+            open_time: self.time,
+            advice: self.advice,
+            margin: self.margin,
+            funding: self.funding,
+            open_fills: self.fills,
+
             close_time: time,
             close_fills: fills,
             close_reason: reason,
@@ -137,20 +472,38 @@ impl OpenShortPosition {
     }
 }
 
-#[derive(Deserialize, Serialize)]
+fn futures_pnl(advice: Advice, open_quote: f64, close_quote: f64) -> f64 {
+    match advice {
+        Advice::Long => close_quote - open_quote,
+        _ => open_quote - close_quote,
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum Position {
     Long(LongPosition),
     Short(ShortPosition),
+    Futures(FuturesPosition),
 }
 
-#[derive(Deserialize, Serialize)]
+impl Position {
+    pub fn close_time(&self) -> Timestamp {
+        match self {
+            Self::Long(pos) => pos.close_time,
+            Self::Short(pos) => pos.close_time,
+            Self::Futures(pos) => pos.close_time,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct LongPosition {
     pub open_time: Timestamp,
-    pub open_fills: [Fill; 1],
+    pub open_fills: Vec<Fill>,
 
     pub close_time: Timestamp,
-    pub close_fills: [Fill; 1],
+    pub close_fills: Vec<Fill>,
     pub close_reason: CloseReason,
 }
 
@@ -180,14 +533,14 @@ impl LongPosition {
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ShortPosition {
     pub open_time: Timestamp,
     pub collateral: f64,
     pub borrowed: f64,
-    pub open_fills: [Fill; 1],
+    pub open_fills: Vec<Fill>,
     pub close_time: Timestamp,
-    pub close_fills: [Fill; 1],
+    pub close_fills: Vec<Fill>,
     pub close_reason: CloseReason,
 }
 
@@ -218,22 +571,267 @@ impl ShortPosition {
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
+pub struct FuturesPosition {
+    pub open_time: Timestamp,
+    // Either `Advice::Long` or `Advice::Short`.
+    pub advice: Advice,
+    pub margin: f64,
+    pub funding: f64,
+    pub open_fills: Vec<Fill>,
+
+    pub close_time: Timestamp,
+    pub close_fills: Vec<Fill>,
+    pub close_reason: CloseReason,
+}
+
+impl FuturesPosition {
+    pub fn cost(&self) -> f64 {
+        self.margin + Fill::total_fee(&self.open_fills)
+    }
+
+    // Quote released from the margin. Losses are limited to the isolated margin.
+    pub fn gain(&self) -> f64 {
+        let pnl = futures_pnl(
+            self.advice,
+            Fill::total_quote(&self.open_fills),
+            Fill::total_quote(&self.close_fills),
+        );
+        f64::max(
+            self.margin - self.funding + pnl - Fill::total_fee(&self.close_fills),
+            0.0,
+        )
+    }
+
+    pub fn profit(&self) -> f64 {
+        self.gain() - self.cost()
+    }
+
+    pub fn duration(&self) -> Interval {
+        self.close_time - self.open_time
+    }
+}
+
+// A limit entry order which was not filled before its timeout or the end of the data.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExpiredOrder {
+    // Either `Advice::Long` or `Advice::Short`.
+    pub advice: Advice,
+    pub price: f64,
+    pub time: Timestamp,
+    pub expire_time: Timestamp,
+}
+
+// A limit entry order taken out of the market before it was filled or expired.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CancelledOrder {
+    // Either `Advice::Long` or `Advice::Short`.
+    pub advice: Advice,
+    pub price: f64,
+    pub time: Timestamp,
+    pub cancel_time: Timestamp,
+    pub reason: CancelReason,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CancelReason {
+    // The strategy advised otherwise.
+    Strategy,
+    // A new entry order was placed in its stead.
+    Replaced,
+}
+
+// Reason the trader stopped trading before running out of candles or, for `OutOfOrder`, rejected a
+// single candle.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Error, PartialEq, Serialize)]
+pub enum TradeError {
+    #[error("position size rounded down to zero")]
+    SizeZero,
+    #[error("short collateral rounded down to zero")]
+    CollateralZero,
+    #[error("candles missing")]
+    Gap,
+    #[error("candle not newer than the previous one")]
+    OutOfOrder,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Halt {
+    pub time: Timestamp,
+    pub error: TradeError,
+}
+
+// Per-candle state of the trader, taken at the candle's close.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CandleRecord {
+    pub time: Timestamp,
+    pub price: f64,
+    pub advice: Advice,
+    // Quote balance plus any open position marked to market at `price`.
+    pub equity: f64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum TradeEvent {
+    Open {
+        time: Timestamp,
+        // Either `Advice::Long` or `Advice::Short`.
+        advice: Advice,
+        price: f64,
+    },
+    Close {
+        time: Timestamp,
+        price: f64,
+        reason: CloseReason,
+        profit: f64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TracePoint {
+    pub time: Timestamp,
+    pub value: f64,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct Recording {
+    pub candles: Vec<CandleRecord>,
+    pub events: Vec<TradeEvent>,
+    // Debug values of the strategy, stop-loss and take-profit at the close of every candle, keyed
+    // by source and name, e.g. `strategy.short_ma`.
+    #[serde(default)]
+    pub trace: HashMap<String, Vec<TracePoint>>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct TradingSummary {
     pub positions: Vec<Position>,
+    pub expired_orders: Vec<ExpiredOrder>,
+    #[serde(default)]
+    pub cancelled_orders: Vec<CancelledOrder>,
+    // Set if trading stopped on an error before the last candle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub halted: Option<Halt>,
+    // Only present if recording was requested in `TradeInput`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording: Option<Recording>,
 
     pub start: Timestamp,
     pub end: Timestamp,
     pub quote: f64,
+
+    // Gaps between consecutive candles and the number of candles missing in them.
+    #[serde(default)]
+    pub num_gaps: u32,
+    #[serde(default)]
+    pub num_missing_candles: u32,
 }
 
 impl TradingSummary {
     pub fn new(start: Timestamp, end: Timestamp, quote: f64) -> Self {
         Self {
             positions: Vec::new(),
+            expired_orders: Vec::new(),
+            cancelled_orders: Vec::new(),
+            halted: None,
+            recording: None,
             start,
             end,
             quote,
+            num_gaps: 0,
+            num_missing_candles: 0,
+        }
+    }
+
+    pub(crate) fn record_candle(&mut self, record: CandleRecord) {
+        if let Some(recording) = &mut self.recording {
+            recording.candles.push(record);
+        }
+    }
+
+    pub(crate) fn record_trace(
+        &mut self,
+        source: &str,
+        time: Timestamp,
+        values: Vec<(Cow<'static, str>, f64)>,
+    ) {
+        if let Some(recording) = &mut self.recording {
+            for (name, value) in values {
+                recording
+                    .trace
+                    .entry(format!("{source}.{name}"))
+                    .or_default()
+                    .push(TracePoint { time, value });
+            }
+        }
+    }
+
+    pub(crate) fn record_event(&mut self, event: TradeEvent) {
+        if let Some(recording) = &mut self.recording {
+            recording.events.push(event);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_margin_multiplier_validated_when_deserialized() {
+        let params =
+            serde_json::from_str::<TraderParams>(r#"{"interval": 1, "margin_multiplier": 0}"#);
+        assert!(params.is_err());
+
+        let ctx = serde_json::from_str::<TraderParamsContext>(
+            r#"{"intervals": [1], "min_margin_multiplier": 1, "max_margin_multiplier": 3}"#,
+        );
+        assert!(ctx.is_err());
+        let ctx = serde_json::from_str::<TraderParamsContext>(
+            r#"{"intervals": [1], "min_margin_multiplier": 3, "max_margin_multiplier": 2}"#,
+        );
+        assert!(ctx.is_err());
+        let ctx = serde_json::from_str::<TraderParamsContext>(r#"{"intervals": [1]}"#);
+        assert_eq!(ctx.unwrap().max_margin_multiplier, 2);
+    }
+
+    fn fill(price: f64, size: f64) -> Fill {
+        Fill {
+            price,
+            size,
+            quote: price * size,
+            fee: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_long_position_aggregates_scaled_fills() {
+        let mut pos = OpenLongPosition::new(0.into(), vec![fill(1.0, 2.0)]);
+        pos.scale_in(vec![fill(2.0, 1.0), fill(2.0, 1.0)]);
+        assert_eq!(pos.cost(), 6.0);
+        assert_eq!(pos.base_balance(), 4.0);
+
+        pos.scale_out(vec![fill(3.0, 1.0)]);
+        assert_eq!(pos.base_balance(), 3.0);
+
+        let pos = pos.close(2.into(), vec![fill(1.0, 3.0)], CloseReason::Strategy);
+        assert_eq!(pos.base_gain(), 4.0);
+        assert_eq!(pos.base_cost(), 4.0);
+        assert_eq!(pos.gain(), 6.0);
+        assert_eq!(pos.profit(), 0.0);
+    }
+
+    #[test]
+    fn test_short_position_aggregates_scaled_fills() {
+        let mut pos = OpenShortPosition::new(0.into(), 2.0, 2.0, vec![fill(1.0, 2.0)]);
+        pos.scale_in(1.0, 1.0, vec![fill(1.0, 1.0)]);
+        pos.scale_out(1.0, vec![fill(0.5, 1.0)]);
+        assert_eq!(pos.outstanding(), 2.0);
+
+        let pos = pos.close(2.into(), vec![fill(0.5, 2.0)], CloseReason::Strategy);
+        assert_eq!(pos.borrowed, 3.0);
+        assert_eq!(pos.cost(), 3.0);
+        assert_eq!(pos.profit(), 1.5);
+    }
+}
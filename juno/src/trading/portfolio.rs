@@ -0,0 +1,400 @@
+use super::{Halt, Position, TradeInput, Trader, TradingParams, TradingSummary};
+use crate::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Determines how much quote a symbol is given from the account when entering a position.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Weighting {
+    // Every position slot is an equal share of account equity.
+    #[default]
+    Equal,
+    // Free quote is split evenly among the position slots still available.
+    Available,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Allocation {
+    #[serde(default)]
+    pub weighting: Weighting,
+    // Maximum number of symbols holding a position at the same time. Defaults to all of them.
+    #[serde(default)]
+    pub max_positions: Option<u32>,
+    // Maximum fraction of account equity committed to a single symbol.
+    #[serde(default = "default_symbol_cap")]
+    pub symbol_cap: f64,
+}
+
+impl Default for Allocation {
+    fn default() -> Self {
+        Self {
+            weighting: Weighting::default(),
+            max_positions: None,
+            symbol_cap: default_symbol_cap(),
+        }
+    }
+}
+
+fn default_symbol_cap() -> f64 {
+    1.0
+}
+
+impl Allocation {
+    fn slots(&self, num_symbols: usize) -> usize {
+        self.max_positions
+            .map_or(num_symbols, |max_positions| max_positions as usize)
+            .min(num_symbols)
+    }
+
+    // Quote handed out to a symbol about to be stepped while `open` slots are taken.
+    fn quote(&self, equity: f64, free_quote: f64, open: usize, slots: usize) -> f64 {
+        if open >= slots {
+            return 0.0;
+        }
+        let quote = match self.weighting {
+            Weighting::Equal => equity / slots as f64,
+            Weighting::Available => free_quote / (slots - open) as f64,
+        };
+        quote.min(equity * self.symbol_cap).min(free_quote).max(0.0)
+    }
+}
+
+pub struct PortfolioInput<'a> {
+    pub symbols: &'a [String],
+    // One per symbol. A symbol's balance is allocated from the account, so its `quote` only
+    // serves as the initial quote of its position sizer and summary.
+    pub inputs: &'a [TradeInput<'a>],
+    pub quote: f64,
+    pub allocation: Allocation,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EquityRecord {
+    pub time: Timestamp,
+    pub equity: f64,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PortfolioSummary {
+    // Positions of all symbols ordered by close time, accounted against the account quote.
+    // Halted at the earliest halt of any symbol.
+    pub account: TradingSummary,
+    pub symbols: HashMap<String, TradingSummary>,
+    // Account equity at the close of every step of the common timeline.
+    pub equity: Vec<EquityRecord>,
+}
+
+// Steps all symbols on a common timeline from a single pool of quote. A symbol is only holding
+// quote while it is in a position or waiting on an entry; an idle symbol returns it to the pool.
+pub fn trade_portfolio(params: &TradingParams, input: &PortfolioInput) -> PortfolioSummary {
+    assert_eq!(input.symbols.len(), input.inputs.len());
+
+    let interval = params.trader.interval;
+    let allocation = input.allocation;
+    let slots = allocation.slots(input.inputs.len());
+
+    let mut traders: Vec<_> = input
+        .inputs
+        .iter()
+        .map(|trade_input| {
+            let mut trader = Trader::new(params, trade_input);
+            trader.withdraw();
+            trader
+        })
+        .collect();
+    let mut free_quote = input.quote;
+    let mut cursors = vec![0; traders.len()];
+    let mut equity = Vec::new();
+
+    while let Some(time) = input
+        .inputs
+        .iter()
+        .zip(&cursors)
+        .filter_map(|(trade_input, &cursor)| trade_input.candles.get(cursor))
+        .map(|candle| candle.time)
+        .min()
+    {
+        for i in 0..traders.len() {
+            let candle = match input.inputs[i].candles.get(cursors[i]) {
+                Some(candle) if candle.time == time => candle,
+                _ => continue,
+            };
+            cursors[i] += 1;
+            if traders[i].summary().halted.is_some() {
+                continue;
+            }
+
+            if traders[i].idle() {
+                let open = traders.iter().filter(|trader| !trader.idle()).count();
+                let account = free_quote + traders.iter().map(Trader::equity).sum::<f64>();
+                let quote = allocation.quote(account, free_quote, open, slots);
+                traders[i].allow_entries(quote > 0.0);
+                traders[i].deposit(quote);
+                free_quote -= quote;
+            }
+
+            // A halted symbol keeps its summary and position but is no longer stepped.
+            let _ = traders[i].tick(candle);
+
+            if traders[i].idle() {
+                free_quote += traders[i].withdraw();
+            }
+        }
+
+        equity.push(EquityRecord {
+            time: time + interval,
+            equity: free_quote + traders.iter().map(Trader::equity).sum::<f64>(),
+        });
+    }
+
+    let start = input
+        .inputs
+        .iter()
+        .filter_map(|trade_input| trade_input.candles.first())
+        .map(|candle| candle.time)
+        .min()
+        .unwrap_or_else(|| 0.into());
+    let end = equity.last().map_or(start + interval, |record| record.time);
+    let mut account = TradingSummary::new(start, end, input.quote);
+
+    let summaries: Vec<_> = traders.into_iter().map(Trader::finish).collect();
+    for summary in summaries.iter() {
+        account.positions.extend(summary.positions.iter().cloned());
+        account
+            .expired_orders
+            .extend(summary.expired_orders.iter().copied());
+        account
+            .cancelled_orders
+            .extend(summary.cancelled_orders.iter().copied());
+        account.num_gaps += summary.num_gaps;
+        account.num_missing_candles += summary.num_missing_candles;
+    }
+    // Stable sorts keep ties in symbol order.
+    account.positions.sort_by_key(Position::close_time);
+    account
+        .expired_orders
+        .sort_by_key(|order| order.expire_time);
+    account
+        .cancelled_orders
+        .sort_by_key(|order| order.cancel_time);
+    account.halted = summaries
+        .iter()
+        .filter_map(|summary| summary.halted)
+        .min_by_key(|halt: &Halt| halt.time);
+    let symbols = input.symbols.iter().cloned().zip(summaries).collect();
+
+    PortfolioSummary {
+        account,
+        symbols,
+        equity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        candle_transform::CandleTransformParams,
+        filters::{Price, Size},
+        indicators::{MAParams, SmaParams},
+        position_sizer::PositionSizerParams,
+        slippage::SlippageParams,
+        stop_loss::{self, StopLossParams},
+        strategies::{FourWeekRuleParams, StrategyParams},
+        take_profit::{self, TakeProfitParams},
+        time_exit::TimeExitParams,
+        trading::{
+            Direction, EntryOrder, ExecutionTiming, ExitTrigger, GapPolicy, Market, ReEntryParams,
+            SessionFilterParams, TraderParams,
+        },
+        BorrowInfo, Candle, Fees, Filters, Interval,
+    };
+
+    const FEES: Fees = Fees {
+        maker: 0.0,
+        taker: 0.0,
+    };
+    const FILTERS: Filters = Filters {
+        price: Price {
+            min: 0.0,
+            max: 0.0,
+            step: 0.0,
+        },
+        size: Size {
+            min: 0.0,
+            max: 0.0,
+            step: 0.0,
+        },
+        base_precision: 8,
+        quote_precision: 8,
+    };
+    const BORROW_INFO: BorrowInfo = BorrowInfo {
+        interest_interval: 1,
+        interest_rate: 0.0,
+        limit: 1.0,
+    };
+
+    // Goes long as soon as the price breaks above the previous close.
+    fn breakout_params() -> TradingParams {
+        TradingParams {
+            strategy: StrategyParams::FourWeekRule(FourWeekRuleParams {
+                period: 1,
+                ma: MAParams::Sma(SmaParams { period: 1 }),
+            }),
+            trader: TraderParams {
+                interval: Interval(1),
+                direction: Direction::Long,
+                margin_multiplier: 2,
+                re_entry: ReEntryParams::default(),
+                session: SessionFilterParams::default(),
+            },
+            stop_loss: StopLossParams::Noop(stop_loss::NoopParams {}),
+            take_profit: TakeProfitParams::Noop(take_profit::NoopParams {}),
+            time_exit: TimeExitParams::default(),
+            position_sizer: PositionSizerParams::default(),
+            candle_transform: CandleTransformParams::default(),
+        }
+    }
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                time: (i as u64).into(),
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    fn input(candles: &[Candle]) -> TradeInput<'_> {
+        TradeInput {
+            candles,
+            fees: &FEES,
+            filters: &FILTERS,
+            borrow_info: &BORROW_INFO,
+            maintenance_margin_level: 1.1,
+            quote: 1.0,
+            exit_trigger: ExitTrigger::Close,
+            execution_timing: ExecutionTiming::Close,
+            entry_order: EntryOrder::Market,
+            slippage: SlippageParams::default(),
+            market: Market::Spot,
+            funding_rates: &[],
+            blackouts: &[],
+            gap_policy: GapPolicy::default(),
+            record: false,
+        }
+    }
+
+    fn run(candles: &[Vec<Candle>], allocation: Allocation) -> PortfolioSummary {
+        let symbols: Vec<_> = (0..candles.len()).map(|i| format!("s{}-usdt", i)).collect();
+        let inputs: Vec<_> = candles.iter().map(|candles| input(candles)).collect();
+        trade_portfolio(
+            &breakout_params(),
+            &PortfolioInput {
+                symbols: &symbols,
+                inputs: &inputs,
+                quote: 1.0,
+                allocation,
+            },
+        )
+    }
+
+    fn costs(summary: &TradingSummary) -> Vec<f64> {
+        summary
+            .positions
+            .iter()
+            .map(|pos| match pos {
+                Position::Long(pos) => pos.cost(),
+                _ => panic!("expected long positions"),
+            })
+            .collect()
+    }
+
+    fn assert_costs(summary: &PortfolioSummary, expected: &[f64]) {
+        for (i, expected) in expected.iter().enumerate() {
+            let costs = costs(&summary.symbols[&format!("s{}-usdt", i)]);
+            assert_eq!(costs.len(), 1);
+            assert!((costs[0] - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_portfolio_caps_concurrent_positions() {
+        let candles = [
+            candles(&[8.0, 8.0, 9.0, 10.0]),
+            candles(&[8.0, 8.0, 9.0, 10.0]),
+        ];
+        let summary = run(
+            &candles,
+            Allocation {
+                max_positions: Some(1),
+                ..Allocation::default()
+            },
+        );
+
+        // The first symbol takes the only slot with the whole account.
+        assert_eq!(costs(&summary.account), [1.0]);
+        assert_eq!(costs(&summary.symbols["s0-usdt"]), [1.0]);
+        assert!(summary.symbols["s1-usdt"].positions.is_empty());
+        assert_eq!(summary.account.start, 0);
+        assert_eq!(summary.account.end, 4);
+        let equity = summary.equity.last().unwrap();
+        assert_eq!(equity.time, 4);
+        assert!((equity.equity - 1.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_portfolio_splits_equity_within_symbol_cap() {
+        let candles = [
+            candles(&[8.0, 8.0, 8.0, 9.0]),
+            candles(&[8.0, 8.0, 9.0, 9.0]),
+        ];
+
+        let summary = run(&candles, Allocation::default());
+        let equal = costs(&summary.account);
+        assert_eq!(equal.len(), 2);
+        assert!(equal.iter().all(|cost| (cost - 0.5).abs() < 1e-6));
+
+        let summary = run(
+            &candles,
+            Allocation {
+                symbol_cap: 0.25,
+                ..Allocation::default()
+            },
+        );
+        let capped = costs(&summary.account);
+        assert_eq!(capped.len(), 2);
+        assert!(capped.iter().all(|cost| (cost - 0.25).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_portfolio_weighting() {
+        // The first symbol enters on the second candle and doubles in value before the others
+        // enter.
+        let candles = [
+            candles(&[8.0, 8.0, 16.0, 16.0]),
+            candles(&[8.0, 7.0, 8.0, 8.0]),
+            candles(&[8.0, 7.0, 8.0, 8.0]),
+        ];
+
+        // A third of the account equity, as long as there is free quote left.
+        let summary = run(&candles, Allocation::default());
+        assert_costs(&summary, &[1.0 / 3.0, 4.0 / 9.0, 2.0 / 9.0]);
+
+        // The free quote split among the remaining slots.
+        let summary = run(
+            &candles,
+            Allocation {
+                weighting: Weighting::Available,
+                ..Allocation::default()
+            },
+        );
+        assert_costs(&summary, &[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]);
+    }
+}
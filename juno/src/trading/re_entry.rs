@@ -0,0 +1,54 @@
+use crate::{genetics::Chromosome, Advice};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Decides when a position may be entered again after one was closed by its stop-loss or
+// take-profit.
+#[derive(ChromosomeEnum, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum ReEntryParams {
+    Cooldown(CooldownParams),
+    FreshSignal(FreshSignalParams),
+    SameDirection(SameDirectionParams),
+}
+
+impl Default for ReEntryParams {
+    fn default() -> Self {
+        Self::FreshSignal(FreshSignalParams {})
+    }
+}
+
+impl ReEntryParams {
+    // Advice to enter on `candles` candles after the exit, given the fresh `advice` of the
+    // strategy (if any) and the `prevailing` one it last gave.
+    pub fn advice(&self, candles: u32, advice: Advice, prevailing: Advice) -> Advice {
+        match self {
+            Self::Cooldown(params) if candles < params.candles => Advice::None,
+            Self::FreshSignal(_) => advice,
+            _ if advice != Advice::None => advice,
+            // Not on the exit candle itself, which would immediately undo the exit.
+            Self::SameDirection(_) if candles == 0 => Advice::None,
+            _ => prevailing,
+        }
+    }
+}
+
+// Ignores any signal for a number of candles after the exit. Afterwards enters on the prevailing
+// signal.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct CooldownParams {
+    pub candles: u32,
+}
+
+fn candles(rng: &mut StdRng) -> u32 {
+    rng.gen_range(1..100)
+}
+
+// Waits for the strategy to give a new signal.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct FreshSignalParams {}
+
+// Enters again on the next candle if the strategy still signals the same direction.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct SameDirectionParams {}
@@ -0,0 +1,116 @@
+use crate::{genetics::Chromosome, Interval, Timestamp};
+use juno_derive::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const ALL_WEEKDAYS: u32 = 0b111_1111;
+
+// Restricts entries to a window of UTC hours on a set of weekdays. The window spans from
+// `start_hour` up to, but excluding, `end_hour`. It wraps around midnight if `start_hour` is past
+// `end_hour` and covers the whole day if both are equal.
+#[derive(Chromosome, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct SessionFilterParams {
+    pub start_hour: u32,
+    pub end_hour: u32,
+    // Bitmask of the weekdays entries are allowed on, Monday being the lowest bit.
+    pub weekdays: u32,
+}
+
+impl Default for SessionFilterParams {
+    fn default() -> Self {
+        Self {
+            start_hour: 0,
+            end_hour: 0,
+            weekdays: ALL_WEEKDAYS,
+        }
+    }
+}
+
+impl SessionFilterParamsContext {
+    // Allows entries at all times.
+    pub fn unrestricted() -> Self {
+        let params = SessionFilterParams::default();
+        Self {
+            start_hour: Some(params.start_hour),
+            end_hour: Some(params.end_hour),
+            weekdays: Some(params.weekdays),
+        }
+    }
+}
+
+fn start_hour(rng: &mut StdRng) -> u32 {
+    rng.gen_range(0..24)
+}
+fn end_hour(rng: &mut StdRng) -> u32 {
+    start_hour(rng)
+}
+fn weekdays(rng: &mut StdRng) -> u32 {
+    rng.gen_range(1..=ALL_WEEKDAYS)
+}
+
+#[derive(Clone, Copy)]
+pub struct SessionFilter {
+    start_hour: u32,
+    end_hour: u32,
+    weekdays: u32,
+}
+
+impl SessionFilter {
+    pub fn new(params: &SessionFilterParams) -> Self {
+        Self {
+            start_hour: params.start_hour,
+            end_hour: params.end_hour,
+            weekdays: params.weekdays,
+        }
+    }
+
+    pub fn allows(&self, time: Timestamp) -> bool {
+        let hour = time.hour();
+        let in_hours = match self.start_hour.cmp(&self.end_hour) {
+            std::cmp::Ordering::Less => hour >= self.start_hour && hour < self.end_hour,
+            std::cmp::Ordering::Greater => hour >= self.start_hour || hour < self.end_hour,
+            std::cmp::Ordering::Equal => true,
+        };
+        in_hours && self.weekdays & (1 << time.weekday()) != 0
+    }
+}
+
+// A period, such as an exchange maintenance, during which no positions are entered. If
+// `flatten` is set, an open position is also closed at the close of the candle the period
+// begins on (or the one right before it, if the period begins on a candle boundary).
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Blackout {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    #[serde(default)]
+    pub flatten: bool,
+}
+
+impl Blackout {
+    pub fn covers(&self, time: Timestamp, interval: Interval) -> bool {
+        time + interval >= self.start && time < self.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_filter_wraps_around_midnight() {
+        // A Tuesday.
+        let day = Timestamp(1_546_300_800_000);
+        let filter = SessionFilter::new(&SessionFilterParams {
+            start_hour: 22,
+            end_hour: 2,
+            // Tuesday only.
+            weekdays: 0b10,
+        });
+        assert!(filter.allows(day + Interval::HOUR_MS));
+        assert!(!filter.allows(day + Interval::HOUR_MS * 2));
+        assert!(!filter.allows(day + Interval::HOUR_MS * 21));
+        assert!(filter.allows(day + Interval::HOUR_MS * 23));
+        // Wednesday.
+        assert!(!filter.allows(day + Interval::HOUR_MS * 25));
+    }
+}
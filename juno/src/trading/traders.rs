@@ -1,378 +1,2469 @@
 use crate::{
+    candle_transform::CandleTransform,
     math::{ceil_multiple, round_down, round_half_up},
+    position_sizer::PositionSizer,
+    slippage::{Slippage, SlippageParams},
     stop_loss::StopLoss,
     strategies::{Signal, StrategyMeta},
     take_profit::TakeProfit,
+    time_exit::TimeExit,
     trading::{
-        CloseReason, OpenLongPosition, OpenPosition, OpenShortPosition, Position, TradingSummary,
+        Blackout, CancelReason, CancelledOrder, CandleRecord, CloseReason, Direction, ExpiredOrder,
+        Halt, OpenFuturesPosition, OpenLongPosition, OpenPosition, OpenShortPosition, Position,
+        ReEntryParams, Recording, SessionFilter, TradeError, TradeEvent, TradingSummary,
     },
     utils::Changed,
-    Advice, BorrowInfo, Candle, Fees, Fill, Filters, Interval, Timestamp,
+    Advice, BorrowInfo, Candle, Fees, Fill, Filters, FundingRate, Interval, Timestamp,
 };
+use serde::{Deserialize, Serialize};
 
-use super::TradingParams;
+use super::{TraderParams, TradingParams};
 
+#[derive(Clone)]
 struct State {
     pub strategy: Box<dyn Signal>,
     pub stop_loss: Box<dyn StopLoss>,
     pub take_profit: Box<dyn TakeProfit>,
+    pub time_exit: Box<dyn TimeExit>,
+    pub slippage: Box<dyn Slippage>,
+    pub position_sizer: Box<dyn PositionSizer>,
+    pub candle_transform: Box<dyn CandleTransform>,
+    pub direction: Direction,
+    pub margin_multiplier: u32,
+    pub re_entry: ReEntryParams,
+    pub session: SessionFilter,
     pub changed: Changed,
     pub quote: f64,
     pub open_position: Option<OpenPosition>,
+    pub pending_action: Option<PendingAction>,
+    pub pending_order: Option<LimitOrder>,
     pub last_candle: Option<Candle>,
+    // Index of the next funding rate to settle.
+    pub funding_index: usize,
+    // Cleared by a portfolio which has no quote to allocate to a new position.
+    pub entries_allowed: bool,
+    // Candles since a position was closed by its stop-loss or take-profit, until the next entry.
+    pub stopped_out: Option<u32>,
 }
 
 impl State {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         quote: f64,
+        trader: &TraderParams,
         strategy: Box<dyn Signal>,
         stop_loss: Box<dyn StopLoss>,
         take_profit: Box<dyn TakeProfit>,
+        time_exit: Box<dyn TimeExit>,
+        slippage: Box<dyn Slippage>,
+        position_sizer: Box<dyn PositionSizer>,
+        candle_transform: Box<dyn CandleTransform>,
     ) -> Self {
         Self {
             strategy,
             stop_loss,
             take_profit,
+            time_exit,
+            slippage,
+            position_sizer,
+            candle_transform,
+            direction: trader.direction,
+            margin_multiplier: trader.margin_multiplier,
+            re_entry: trader.re_entry,
+            session: SessionFilter::new(&trader.session),
             quote,
             changed: Changed::new(true),
             open_position: None,
+            pending_action: None,
+            pending_order: None,
             last_candle: None,
+            funding_index: 0,
+            entries_allowed: true,
+            stopped_out: None,
+        }
+    }
+}
+
+// An action decided on a candle's close which is waiting for the next candle's open.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PendingAction {
+    pub close: Option<CloseReason>,
+    // Either `Advice::Long` or `Advice::Short`.
+    pub open: Option<Advice>,
+    pub scale: Option<Scale>,
+}
+
+// Market order adding to or partially exiting a spot position, as chosen by the position sizer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+    // Quote committed to the position.
+    In(f64),
+    // Fraction of the position exited.
+    Out(f64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LimitOrder {
+    // Either `Advice::Long` or `Advice::Short`.
+    pub advice: Advice,
+    pub price: f64,
+    pub time: Timestamp,
+    // Number of candles the order is still allowed to rest in the market for.
+    pub candles_left: u32,
+}
+
+impl LimitOrder {
+    fn expire(self, expire_time: Timestamp) -> ExpiredOrder {
+        ExpiredOrder {
+            advice: self.advice,
+            price: self.price,
+            time: self.time,
+            expire_time,
+        }
+    }
+
+    fn cancel(self, cancel_time: Timestamp, reason: CancelReason) -> CancelledOrder {
+        CancelledOrder {
+            advice: self.advice,
+            price: self.price,
+            time: self.time,
+            cancel_time,
+            reason,
+        }
+    }
+}
+
+// Determines how positions are entered. Exits are always market orders.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum EntryOrder {
+    // Filled immediately at the execution price as a taker.
+    #[default]
+    Market,
+    // Post-only limit order placed at `offset` (a fraction of the execution price) below it for
+    // longs and above it for shorts. Filled as a maker at the limit price by the first of the
+    // next `timeout` candles whose low (for longs) or high (for shorts) reaches it.
+    Limit {
+        offset: f64,
+        timeout: u32,
+    },
+}
+
+// Determines the instrument positions are traded with.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum Market {
+    // Longs buy the base asset and shorts borrow it on margin.
+    #[default]
+    Spot,
+    // Perpetual futures on isolated margin, leveraged `margin_multiplier` times in both
+    // directions. A position is liquidated once its margin falls to `maintenance_margin_rate`
+    // of its notional value.
+    Futures {
+        maintenance_margin_rate: f64,
+    },
+}
+
+// Determines when an action decided on a candle's close is filled.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ExecutionTiming {
+    // Filled at the close of the same candle that produced the advice.
+    #[default]
+    Close,
+    // Queued and filled at the open of the next candle. Avoids same-bar lookahead.
+    NextOpen,
+}
+
+// Determines how candles missing between two consecutive candles are dealt with.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum GapPolicy {
+    // Trading continues with the next candle as if nothing was missing. The strategy is still fed
+    // forward-filled candles so that it never sees a gap itself.
+    #[default]
+    Skip,
+    // Every missing candle is replaced by a flat candle at the previous close without volume.
+    ForwardFill,
+    // The open position is closed at the previous close and trading halts.
+    Halt,
+}
+
+// Determines at which price stop-loss and take-profit exits are evaluated and filled.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum ExitTrigger {
+    // Levels are compared against candle close and filled at close.
+    #[default]
+    Close,
+    // Levels are compared against candle high and low and filled at the level itself (or at
+    // open if the candle gapped through it).
+    HighLow {
+        priority: ExitPriority,
+    },
+}
+
+// Decides which exit is assumed to have happened first when both the stop-loss and the
+// take-profit levels were crossed within the same candle.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ExitPriority {
+    #[default]
+    StopLoss,
+    TakeProfit,
+    NearestToOpen,
+}
+
+impl ExitPriority {
+    fn stop_loss_first(&self, open: f64, stop_loss_price: f64, take_profit_price: f64) -> bool {
+        match self {
+            Self::StopLoss => true,
+            Self::TakeProfit => false,
+            // Ties are resolved pessimistically.
+            Self::NearestToOpen => {
+                f64::abs(open - stop_loss_price) <= f64::abs(open - take_profit_price)
+            }
         }
     }
 }
 
+// Used by requests which do not set a maintenance margin level.
+pub fn default_maintenance_margin_level() -> f64 {
+    1.1
+}
+
 pub struct TradeInput<'a> {
     pub candles: &'a [Candle],
     pub fees: &'a Fees,
     pub filters: &'a Filters,
     pub borrow_info: &'a BorrowInfo,
-    pub margin_multiplier: u32,
+    // Margin level (assets over debt, both in quote) at or below which a short position is
+    // forcefully liquidated.
+    pub maintenance_margin_level: f64,
     pub quote: f64,
-    pub long: bool,
-    pub short: bool,
+    pub exit_trigger: ExitTrigger,
+    pub execution_timing: ExecutionTiming,
+    pub entry_order: EntryOrder,
+    pub slippage: SlippageParams,
+    pub market: Market,
+    // Funding rates ordered by time. Only settled against futures positions.
+    pub funding_rates: &'a [FundingRate],
+    pub blackouts: &'a [Blackout],
+    pub gap_policy: GapPolicy,
+    // Whether to fill `TradingSummary::recording` with a per-candle series and trade events.
+    pub record: bool,
 }
 
 pub fn trade(params: &TradingParams, input: &TradeInput) -> TradingSummary {
-    let interval = params.trader.interval;
+    let mut trader = Trader::new(params, input);
+    for candle in input.candles {
+        match trader.tick(candle) {
+            Ok(()) | Err(TradeError::OutOfOrder) => {}
+            Err(_) => break,
+        }
+    }
+    let mut summary = trader.finish();
+    // A run which was cut short still spans all of the input.
+    if let Some(last_candle) = input.candles.last() {
+        summary.end = last_candle.time + params.trader.interval;
+    }
+    summary
+}
 
-    let candles_len = input.candles.len();
-    let (start, end) = if candles_len == 0 {
-        (0.into(), Timestamp(interval.0))
-    } else {
-        (
-            input.candles[0].time,
-            input.candles[candles_len - 1].time + interval,
-        )
-    };
+// Trades one candle at a time. Drives `trade` but can also be fed from a live candle source, in
+// which case `TradeInput::candles` is ignored.
+pub struct Trader<'a> {
+    input: &'a TradeInput<'a>,
+    interval: Interval,
+    state: State,
+    summary: TradingSummary,
+}
 
-    let strategy_meta = StrategyMeta { interval };
+// Full trading state, including strategy, stop-loss and take-profit internals, captured by
+// `Trader::snapshot`.
+#[derive(Clone)]
+pub struct TraderSnapshot {
+    state: State,
+    summary: TradingSummary,
+}
 
-    let mut summary = TradingSummary::new(start, end, input.quote);
-    let mut state = State::new(
-        input.quote,
-        params.strategy.construct(&strategy_meta),
-        params.stop_loss.construct(),
-        params.take_profit.construct(),
-    );
+impl<'a> Trader<'a> {
+    pub fn new(params: &TradingParams, input: &'a TradeInput<'a>) -> Self {
+        let interval = params.trader.interval;
+        let strategy_meta = StrategyMeta { interval };
 
-    for candle in input.candles {
-        if tick(
-            &mut state,
-            &mut summary,
-            input.fees,
-            input.filters,
-            input.borrow_info,
-            input.margin_multiplier,
+        let mut summary = TradingSummary::new(0.into(), Timestamp(interval.0), input.quote);
+        if input.record {
+            summary.recording = Some(Recording::default());
+        }
+
+        Self {
+            input,
             interval,
-            input.long,
-            input.short,
+            state: State::new(
+                input.quote,
+                &params.trader,
+                params.strategy.construct(&strategy_meta),
+                params.stop_loss.construct(),
+                params.take_profit.construct(),
+                params.time_exit.construct(),
+                input.slippage.construct(),
+                params.position_sizer.construct(input.quote),
+                params.candle_transform.construct(),
+            ),
+            summary,
+        }
+    }
+
+    // Once an error is returned the trader is halted and rejects any further candles. The exception
+    // is a candle which is not newer than the previous one, such as a re-sent one. It is rejected
+    // with `TradeError::OutOfOrder` and leaves the trader as it was.
+    pub fn tick(&mut self, candle: &Candle) -> Result<(), TradeError> {
+        if let Some(halt) = self.summary.halted {
+            return Err(halt.error);
+        }
+        if matches!(self.state.last_candle, Some(last_candle) if candle.time <= last_candle.time) {
+            return Err(TradeError::OutOfOrder);
+        }
+        if self.state.last_candle.is_none() {
+            self.summary.start = candle.time;
+        }
+        self.summary.end = candle.time + self.interval;
+        tick(
+            &mut self.state,
+            &mut self.summary,
+            self.input,
+            self.interval,
             candle,
         )
-        .is_err()
-        {
-            break;
-        }
+        .inspect_err(|&error| {
+            self.summary.halted = Some(Halt {
+                time: candle.time,
+                error,
+            });
+        })
     }
 
-    if let Some(last_candle) = state.last_candle {
-        match state.open_position {
-            Some(OpenPosition::Long(_)) => close_long_position(
-                &mut state,
-                &mut summary,
-                input.fees,
-                input.filters,
-                last_candle.time + interval,
-                last_candle.close,
-                CloseReason::Cancelled,
+    pub fn quote(&self) -> f64 {
+        self.state.quote
+    }
+
+    pub fn open_position(&self) -> Option<&OpenPosition> {
+        self.state.open_position.as_ref()
+    }
+
+    pub fn pending_action(&self) -> Option<PendingAction> {
+        self.state.pending_action
+    }
+
+    pub fn pending_order(&self) -> Option<LimitOrder> {
+        self.state.pending_order
+    }
+
+    // Quote balance plus the open position marked to market at the last candle's close.
+    pub fn equity(&self) -> f64 {
+        match self.state.last_candle {
+            Some(candle) => equity(
+                &self.state,
+                self.input,
+                candle.time + self.interval,
+                candle.close,
             ),
-            Some(OpenPosition::Short(_)) => close_short_position(
-                &mut state,
-                &mut summary,
-                input.fees,
-                input.filters,
-                input.borrow_info,
+            None => self.state.quote,
+        }
+    }
+
+    // Whether the trader neither holds a position nor waits on an entry.
+    pub fn idle(&self) -> bool {
+        self.state.open_position.is_none()
+            && self.state.pending_action.is_none()
+            && self.state.pending_order.is_none()
+    }
+
+    pub(crate) fn deposit(&mut self, quote: f64) {
+        self.state.quote += quote;
+    }
+
+    pub(crate) fn withdraw(&mut self) -> f64 {
+        std::mem::take(&mut self.state.quote)
+    }
+
+    pub(crate) fn allow_entries(&mut self, allowed: bool) {
+        self.state.entries_allowed = allowed;
+    }
+
+    pub fn summary(&self) -> &TradingSummary {
+        &self.summary
+    }
+
+    pub fn snapshot(&self) -> TraderSnapshot {
+        TraderSnapshot {
+            state: self.state.clone(),
+            summary: self.summary.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: TraderSnapshot) {
+        self.state = snapshot.state;
+        self.summary = snapshot.summary;
+    }
+
+    // Expires any resting order and closes the open position at the last candle's close.
+    // Any action still pending has no candle left to be filled on and is dropped.
+    pub fn finish(mut self) -> TradingSummary {
+        let (state, summary, interval) = (&mut self.state, &mut self.summary, self.interval);
+        if let Some(last_candle) = state.last_candle {
+            if let Some(order) = state.pending_order.take() {
+                summary
+                    .expired_orders
+                    .push(order.expire(last_candle.time + interval));
+            }
+            close_position(
+                state,
+                summary,
+                self.input,
+                &last_candle,
                 last_candle.time + interval,
                 last_candle.close,
                 CloseReason::Cancelled,
-            ),
-            None => {}
+            );
         }
+        self.summary
     }
-
-    summary
 }
 
-#[allow(clippy::too_many_arguments)]
 fn tick(
-    mut state: &mut State,
+    state: &mut State,
     summary: &mut TradingSummary,
-    fees: &Fees,
-    filters: &Filters,
-    borrow_info: &BorrowInfo,
-    margin_multiplier: u32,
+    input: &TradeInput,
     interval: Interval,
-    long: bool,
-    short: bool,
     candle: &Candle,
-) -> Result<(), &'static str> {
-    state.stop_loss.update(candle);
-    state.take_profit.update(candle);
-    state.strategy.update(candle);
-    let advice = state.changed.update(state.strategy.advice());
+) -> Result<(), TradeError> {
+    fill_gap(state, summary, input, interval, candle)?;
+    state.stopped_out = state.stopped_out.map(|candles| candles + 1);
 
-    if let Some(OpenPosition::Long(_)) = state.open_position {
-        if advice == Advice::Short || advice == Advice::Liquidate {
-            close_long_position(
-                state,
-                summary,
-                fees,
-                filters,
-                candle.time + interval,
-                candle.close,
-                CloseReason::Strategy,
-            )
-        } else if state.stop_loss.upside_hit() {
-            close_long_position(
-                state,
-                summary,
-                fees,
-                filters,
-                candle.time + interval,
-                candle.close,
-                CloseReason::StopLoss,
-            )
-        } else if state.take_profit.upside_hit() {
-            close_long_position(
-                state,
-                summary,
-                fees,
-                filters,
-                candle.time + interval,
-                candle.close,
-                CloseReason::TakeProfit,
-            )
+    // Actions decided on the previous candle's close are filled at this candle's open.
+    if let Some(action) = state.pending_action.take() {
+        execute(
+            state,
+            summary,
+            input,
+            candle,
+            candle.time,
+            candle.open,
+            action,
+        )?;
+    }
+
+    if let Some(order) = state.pending_order.take() {
+        let reached = match order.advice {
+            Advice::Long => candle.low <= order.price,
+            _ => candle.high >= order.price,
+        };
+        if reached {
+            fill_limit_order(state, summary, input, candle.time + interval, order)?;
+        } else if order.candles_left > 1 {
+            state.pending_order = Some(LimitOrder {
+                candles_left: order.candles_left - 1,
+                ..order
+            });
+        } else {
+            summary
+                .expired_orders
+                .push(order.expire(candle.time + interval));
         }
-    } else if let Some(OpenPosition::Short(_)) = state.open_position {
-        if advice == Advice::Long || advice == Advice::Liquidate {
-            close_short_position(
-                state,
-                summary,
-                fees,
-                filters,
-                borrow_info,
-                candle.time + interval,
-                candle.close,
-                CloseReason::Strategy,
-            )
-        } else if state.stop_loss.downside_hit() {
-            close_short_position(
-                state,
-                summary,
-                fees,
-                filters,
-                borrow_info,
-                candle.time + interval,
-                candle.close,
-                CloseReason::StopLoss,
-            )
-        } else if state.take_profit.downside_hit() {
-            close_short_position(
+    }
+
+    // Levels are evaluated before the candle is fed to stop-loss and take-profit because these
+    // were the levels resting in the market while the candle was forming.
+    if let ExitTrigger::HighLow { priority } = input.exit_trigger {
+        if let Some((price, reason)) = intra_candle_exit(state, priority, candle) {
+            close_position(
                 state,
                 summary,
-                fees,
-                filters,
-                borrow_info,
+                input,
+                candle,
                 candle.time + interval,
-                candle.close,
-                CloseReason::TakeProfit,
-            )
+                price,
+                reason,
+            );
         }
     }
+    if let Some(price) = liquidation_price(state, input, interval, candle) {
+        close_position(
+            state,
+            summary,
+            input,
+            candle,
+            candle.time + interval,
+            price,
+            CloseReason::Liquidation,
+        );
+    }
+    settle_funding(state, input, interval, candle);
+    let exit_on_close = input.exit_trigger == ExitTrigger::Close;
 
-    if state.open_position.is_none() {
-        if long && advice == Advice::Long {
-            try_open_long_position(state, fees, filters, candle.time + interval, candle.close)?;
-        } else if short && advice == Advice::Short {
-            try_open_short_position(
+    state.stop_loss.update(candle);
+    state.take_profit.update(candle);
+    state.time_exit.update(candle);
+    state.position_sizer.update(candle);
+    state
+        .strategy
+        .update(&state.candle_transform.transform(candle));
+    let advice = state.changed.update(state.strategy.advice());
+
+    // A resting entry order no longer backed by the strategy is cancelled.
+    if let Some(order) = state.pending_order {
+        if advice != Advice::None && advice != order.advice {
+            state.pending_order = None;
+            summary
+                .cancelled_orders
+                .push(order.cancel(candle.time + interval, CancelReason::Strategy));
+        }
+    }
+
+    // Nothing is entered during a blackout. Positions are closed if any of the blackouts asks to.
+    let (blackout, flatten) = input
+        .blackouts
+        .iter()
+        .filter(|blackout| blackout.covers(candle.time, interval))
+        .fold((false, false), |(_, flatten), blackout| {
+            (true, flatten || blackout.flatten)
+        });
+    if blackout {
+        if let Some(order) = state.pending_order.take() {
+            summary
+                .expired_orders
+                .push(order.expire(candle.time + interval));
+        }
+    }
+    if flatten {
+        close_position(
+            state,
+            summary,
+            input,
+            candle,
+            candle.time + interval,
+            candle.close,
+            CloseReason::Blackout,
+        );
+    }
+
+    let close = match state.open_position.as_ref().map(OpenPosition::advice) {
+        Some(Advice::Long) => {
+            if advice == Advice::Short || advice == Advice::Liquidate {
+                Some(CloseReason::Strategy)
+            } else if exit_on_close && state.stop_loss.upside_hit() {
+                Some(CloseReason::StopLoss)
+            } else if exit_on_close && state.take_profit.upside_hit() {
+                Some(CloseReason::TakeProfit)
+            } else if state.time_exit.upside_hit() {
+                Some(CloseReason::TimeExit)
+            } else {
+                None
+            }
+        }
+        Some(_) => {
+            if advice == Advice::Long || advice == Advice::Liquidate {
+                Some(CloseReason::Strategy)
+            } else if exit_on_close && state.stop_loss.downside_hit() {
+                Some(CloseReason::StopLoss)
+            } else if exit_on_close && state.take_profit.downside_hit() {
+                Some(CloseReason::TakeProfit)
+            } else if state.time_exit.downside_hit() {
+                Some(CloseReason::TimeExit)
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
+
+    let flat = state.open_position.is_none() || close.is_some();
+    let entry = match state.stopped_out {
+        Some(candles) => {
+            let prevailing = state.changed.prevailing_advice();
+            state.re_entry.advice(candles, advice, prevailing)
+        }
+        None => advice,
+    };
+    let entries_allowed = state.entries_allowed && !blackout && state.session.allows(candle.time);
+    let open = if flat
+        && entries_allowed
+        && ((state.direction.long() && entry == Advice::Long)
+            || (state.direction.short() && entry == Advice::Short))
+    {
+        state.stopped_out = None;
+        Some(entry)
+    } else {
+        None
+    };
+
+    let scale = if flat {
+        None
+    } else {
+        scale(state, candle.close, entries_allowed)
+    };
+
+    if close.is_some() || open.is_some() || scale.is_some() {
+        let action = PendingAction { close, open, scale };
+        match input.execution_timing {
+            ExecutionTiming::Close => execute(
                 state,
-                fees,
-                filters,
-                borrow_info,
-                margin_multiplier,
+                summary,
+                input,
+                candle,
                 candle.time + interval,
                 candle.close,
-            )?;
+                action,
+            )?,
+            ExecutionTiming::NextOpen => state.pending_action = Some(action),
         }
+    }
+
+    if flat {
         state.stop_loss.clear(candle);
         state.take_profit.clear(candle);
+        state.time_exit.clear(candle);
+    }
+
+    if summary.recording.is_some() {
+        summary.record_candle(CandleRecord {
+            time: candle.time,
+            price: candle.close,
+            advice: state.strategy.advice(),
+            equity: equity(state, input, candle.time + interval, candle.close),
+        });
+        summary.record_trace("strategy", candle.time, state.strategy.debug_values());
+        summary.record_trace("stop_loss", candle.time, state.stop_loss.debug_values());
+        summary.record_trace("take_profit", candle.time, state.take_profit.debug_values());
+        summary.record_trace("time_exit", candle.time, state.time_exit.debug_values());
     }
 
     state.last_candle = Some(*candle);
     Ok(())
 }
 
-fn try_open_long_position(
+// Counts the candles missing before `candle` and deals with them according to the gap policy.
+fn fill_gap(
     state: &mut State,
-    fees: &Fees,
-    filters: &Filters,
-    time: Timestamp,
-    price: f64,
-) -> Result<(), &'static str> {
-    let size = filters.size.round_down(state.quote / price);
-    if size == 0.0 {
-        return Err("size 0");
+    summary: &mut TradingSummary,
+    input: &TradeInput,
+    interval: Interval,
+    candle: &Candle,
+) -> Result<(), TradeError> {
+    let last_candle = match state.last_candle {
+        Some(last_candle) => last_candle,
+        None => return Ok(()),
+    };
+    let missing = ((candle.time - last_candle.time).0 / interval.0).saturating_sub(1);
+    if missing == 0 {
+        return Ok(());
     }
+    summary.num_gaps += 1;
+    summary.num_missing_candles += missing as u32;
 
-    let quote = round_down(price * size, filters.quote_precision);
-    let fee = round_half_up(size * fees.taker, filters.base_precision);
-
-    state.open_position = Some(OpenPosition::Long(OpenLongPosition {
-        time,
-        fills: [Fill {
-            price,
-            size,
-            quote,
-            fee,
-        }],
-    }));
-    state.quote -= quote;
-
+    let filled = (1..=missing).map(|i| {
+        let price = last_candle.close;
+        Candle {
+            time: last_candle.time + interval * i,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0.0,
+        }
+    });
+    match input.gap_policy {
+        // Nothing is traded on the filled candles, so the advice they lead to is only picked up on
+        // the next candle.
+        GapPolicy::Skip => {
+            for filled in filled {
+                state
+                    .strategy
+                    .update(&state.candle_transform.transform(&filled));
+            }
+        }
+        GapPolicy::ForwardFill => {
+            for filled in filled {
+                tick(state, summary, input, interval, &filled)?;
+            }
+        }
+        GapPolicy::Halt => {
+            let time = last_candle.time + interval;
+            state.pending_action = None;
+            if let Some(order) = state.pending_order.take() {
+                summary.expired_orders.push(order.expire(time));
+            }
+            close_position(
+                state,
+                summary,
+                input,
+                &last_candle,
+                time,
+                last_candle.close,
+                CloseReason::Cancelled,
+            );
+            return Err(TradeError::Gap);
+        }
+    }
     Ok(())
 }
 
-fn close_long_position(
+fn execute(
     state: &mut State,
     summary: &mut TradingSummary,
-    fees: &Fees,
-    filters: &Filters,
+    input: &TradeInput,
+    candle: &Candle,
     time: Timestamp,
     price: f64,
-    reason: CloseReason,
-) {
-    if let Some(OpenPosition::Long(pos)) = state.open_position.take() {
-        let size = filters.size.round_down(pos.base_gain());
-
-        let quote = round_down(price * size, filters.quote_precision);
-        let fee = round_half_up(quote * fees.taker, filters.quote_precision);
-
-        let pos = pos.close(
-            time,
-            [Fill {
-                price,
-                size,
-                quote,
-                fee,
-            }],
-            reason,
-        );
-        summary.positions.push(Position::Long(pos));
+    action: PendingAction,
+) -> Result<(), TradeError> {
+    if let Some(reason) = action.close {
+        close_position(state, summary, input, candle, time, price, reason);
+    }
+    if let Some(scale) = action.scale {
+        scale_position(state, input, candle, time, price, scale);
+    }
 
-        state.open_position = None;
-        state.quote += quote - fee;
-    } else {
-        // TODO: Refactor to get rid of this.
-        panic!();
+    if state.open_position.is_none() {
+        match (action.open, input.entry_order) {
+            (Some(advice), EntryOrder::Market) if input.market != Market::Spot => {
+                let margin = entry_quote(state, price);
+                let size = margin * state.margin_multiplier as f64 / price;
+                let price = match advice {
+                    Advice::Long => state.slippage.buy_price(candle, price, size),
+                    _ => state.slippage.sell_price(candle, price, size),
+                };
+                let fee_rate = input.fees.taker;
+                try_open_futures_position(state, input, time, advice, price, margin, fee_rate)?
+            }
+            (Some(Advice::Long), EntryOrder::Market) => {
+                let quote = entry_quote(state, price);
+                let price = state.slippage.buy_price(candle, price, quote / price);
+                try_open_long_position(state, input, time, price, quote, input.fees.taker)?
+            }
+            (Some(Advice::Short), EntryOrder::Market) => {
+                let collateral = entry_quote(state, price);
+                try_open_short_position(
+                    state,
+                    input,
+                    candle,
+                    time,
+                    price,
+                    collateral,
+                    input.fees.taker,
+                )?
+            }
+            (Some(advice), EntryOrder::Limit { offset, timeout }) if timeout > 0 => {
+                let price = match advice {
+                    Advice::Long => price * (1.0 - offset),
+                    _ => price * (1.0 + offset),
+                };
+                let order = LimitOrder {
+                    advice,
+                    price,
+                    time,
+                    candles_left: timeout,
+                };
+                if let Some(replaced) = state.pending_order.replace(order) {
+                    summary
+                        .cancelled_orders
+                        .push(replaced.cancel(time, CancelReason::Replaced));
+                }
+            }
+            _ => {}
+        }
+        record_open(state, summary);
     }
+
+    Ok(())
 }
 
-fn try_open_short_position(
+// Limit orders are filled at their own price without slippage.
+fn fill_limit_order(
     state: &mut State,
-    fees: &Fees,
-    filters: &Filters,
-    borrow_info: &BorrowInfo,
-    margin_multiplier: u32,
+    summary: &mut TradingSummary,
+    input: &TradeInput,
     time: Timestamp,
-    price: f64,
-) -> Result<(), &'static str> {
-    let collateral_size = filters.size.round_down(state.quote / price);
-    if collateral_size == 0.0 {
-        return Err("collateral 0");
+    order: LimitOrder,
+) -> Result<(), TradeError> {
+    let quote = entry_quote(state, order.price);
+    match order.advice {
+        advice if input.market != Market::Spot => try_open_futures_position(
+            state,
+            input,
+            time,
+            advice,
+            order.price,
+            quote,
+            input.fees.maker,
+        )?,
+        Advice::Long => {
+            try_open_long_position(state, input, time, order.price, quote, input.fees.maker)?
+        }
+        _ => {
+            if let Some(borrowed) = short_borrow_size(state, input, order.price, quote)? {
+                let fee_rate = input.fees.maker;
+                open_short_position(state, input, time, order.price, quote, borrowed, fee_rate);
+            }
+        }
     }
-    let borrowed = f64::min(
-        collateral_size * (margin_multiplier - 1) as f64,
-        borrow_info.limit,
-    );
+    record_open(state, summary);
+    Ok(())
+}
 
-    let quote = round_down(price * borrowed, filters.quote_precision);
-    let fee = round_half_up(quote * fees.taker, filters.quote_precision);
+fn record_open(state: &State, summary: &mut TradingSummary) {
+    let event = match &state.open_position {
+        Some(OpenPosition::Long(pos)) => TradeEvent::Open {
+            time: pos.time,
+            advice: Advice::Long,
+            price: pos.fills[0].price,
+        },
+        Some(OpenPosition::Short(pos)) => TradeEvent::Open {
+            time: pos.time,
+            advice: Advice::Short,
+            price: pos.fills[0].price,
+        },
+        Some(OpenPosition::Futures(pos)) => TradeEvent::Open {
+            time: pos.time,
+            advice: pos.advice,
+            price: pos.fills[0].price,
+        },
+        None => return,
+    };
+    summary.record_event(event);
+}
 
-    state.open_position = Some(OpenPosition::Short(OpenShortPosition {
-        time,
-        collateral: state.quote,
-        borrowed,
-        fills: [Fill {
-            price,
-            size: borrowed,
-            quote,
-            fee,
-        }],
-    }));
+// Quote balance plus the open position marked to market at `price`. Exit fees are ignored.
+fn equity(state: &State, input: &TradeInput, time: Timestamp, price: f64) -> f64 {
+    match &state.open_position {
+        Some(OpenPosition::Long(pos)) => state.quote + pos.base_balance() * price,
+        Some(OpenPosition::Short(pos)) => {
+            state.quote - (pos.outstanding() + interest(input, pos, time)) * price
+        }
+        Some(OpenPosition::Futures(pos)) => {
+            state.quote + f64::max(pos.margin - pos.funding + pos.pnl(price), 0.0)
+        }
+        None => state.quote,
+    }
+}
 
-    state.quote += quote - fee;
-    Ok(())
+// Quote the position sizer commits to a new position, capped by the available quote.
+fn entry_quote(state: &State, price: f64) -> f64 {
+    f64::min(state.position_sizer.quote(state.quote, price), state.quote)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn close_short_position(
+fn close_position(
     state: &mut State,
     summary: &mut TradingSummary,
-    fees: &Fees,
-    filters: &Filters,
-    borrow_info: &BorrowInfo,
+    input: &TradeInput,
+    candle: &Candle,
     time: Timestamp,
     price: f64,
     reason: CloseReason,
 ) {
-    if let Some(OpenPosition::Short(pos)) = state.open_position.take() {
-        let borrowed = pos.borrowed;
+    if state.open_position.is_some()
+        && (reason == CloseReason::StopLoss || reason == CloseReason::TakeProfit)
+    {
+        state.stopped_out = Some(0);
+    }
+    match state.open_position {
+        Some(OpenPosition::Long(_)) => {
+            close_long_position(state, summary, input, candle, time, price, reason)
+        }
+        Some(OpenPosition::Short(_)) => {
+            close_short_position(state, summary, input, candle, time, price, reason)
+        }
+        Some(OpenPosition::Futures(_)) => {
+            close_futures_position(state, summary, input, candle, time, price, reason)
+        }
+        None => {}
+    }
+}
 
-        let duration = ceil_multiple(time.0 - pos.time.0, borrow_info.interest_interval)
-            / borrow_info.interest_interval;
-        let interest = round_half_up(
-            borrowed * duration as f64 * borrow_info.interest_rate,
-            filters.base_precision,
-        );
+fn intra_candle_exit(
+    state: &State,
+    priority: ExitPriority,
+    candle: &Candle,
+) -> Option<(f64, CloseReason)> {
+    // If the candle opened beyond a level, the fill happens at open instead.
+    let (stop_loss, take_profit) = match state.open_position.as_ref().map(OpenPosition::advice) {
+        Some(Advice::Long) => (
+            state
+                .stop_loss
+                .upside_price()
+                .filter(|&price| candle.low <= price)
+                .map(|price| f64::min(price, candle.open)),
+            state
+                .take_profit
+                .upside_price()
+                .filter(|&price| candle.high >= price)
+                .map(|price| f64::max(price, candle.open)),
+        ),
+        Some(_) => (
+            state
+                .stop_loss
+                .downside_price()
+                .filter(|&price| candle.high >= price)
+                .map(|price| f64::max(price, candle.open)),
+            state
+                .take_profit
+                .downside_price()
+                .filter(|&price| candle.low <= price)
+                .map(|price| f64::min(price, candle.open)),
+        ),
+        None => return None,
+    };
 
-        let mut size = borrowed + interest;
-        let fee = round_half_up(size * fees.taker, filters.base_precision);
-        size += fee;
-        let quote = round_down(price * size, filters.quote_precision);
+    match (stop_loss, take_profit) {
+        (Some(stop_loss), Some(take_profit)) => {
+            if priority.stop_loss_first(candle.open, stop_loss, take_profit) {
+                Some((stop_loss, CloseReason::StopLoss))
+            } else {
+                Some((take_profit, CloseReason::TakeProfit))
+            }
+        }
+        (Some(stop_loss), None) => Some((stop_loss, CloseReason::StopLoss)),
+        (None, Some(take_profit)) => Some((take_profit, CloseReason::TakeProfit)),
+        (None, None) => None,
+    }
+}
 
-        let pos = pos.close(
-            time,
-            [Fill {
-                price,
-                size,
-                quote,
-                fee,
-            }],
-            reason,
-        );
+// Returns the fill price if the candle's high (or low for futures longs) pushed an open leveraged
+// position's margin down to the maintenance level. The fill happens at the level's price, or at
+// open on a gap.
+fn liquidation_price(
+    state: &State,
+    input: &TradeInput,
+    interval: Interval,
+    candle: &Candle,
+) -> Option<f64> {
+    match (&state.open_position, input.market) {
+        (Some(OpenPosition::Short(pos)), _) => {
+            let debt = pos.outstanding() + interest(input, pos, candle.time + interval);
+            let price = pos.assets() / (debt * input.maintenance_margin_level);
+            (candle.high >= price).then(|| f64::max(price, candle.open))
+        }
+        (
+            Some(OpenPosition::Futures(pos)),
+            Market::Futures {
+                maintenance_margin_rate,
+            },
+        ) => {
+            let price = pos.liquidation_price(maintenance_margin_rate);
+            match pos.advice {
+                Advice::Long => (candle.low <= price).then(|| f64::min(price, candle.open)),
+                _ => (candle.high >= price).then(|| f64::max(price, candle.open)),
+            }
+        }
+        _ => None,
+    }
+}
+
+// Settles the funding rates falling within the candle against an open futures position, marked
+// at the candle's close.
+fn settle_funding(state: &mut State, input: &TradeInput, interval: Interval, candle: &Candle) {
+    let end = candle.time + interval;
+    while let Some(funding) = input.funding_rates.get(state.funding_index) {
+        if funding.time >= end {
+            break;
+        }
+        state.funding_index += 1;
+        if funding.time < candle.time {
+            continue;
+        }
+        if let Some(OpenPosition::Futures(pos)) = &mut state.open_position {
+            pos.pay_funding(funding.rate, candle.close);
+        }
+    }
+}
+
+// Interest accrued on the outstanding part of the loan, in base asset, up until `time`.
+fn interest(input: &TradeInput, pos: &OpenShortPosition, time: Timestamp) -> f64 {
+    let borrow_info = input.borrow_info;
+    let duration = ceil_multiple(time.0 - pos.time.0, borrow_info.interest_interval)
+        / borrow_info.interest_interval;
+    round_half_up(
+        pos.outstanding() * duration as f64 * borrow_info.interest_rate,
+        input.filters.base_precision,
+    )
+}
+
+// The position sizer chose a quote which rounds down to zero size although the whole balance would
+// not. The entry is skipped as if the sizer had chosen nothing.
+fn sized_below_minimum(state: &State, input: &TradeInput, quote: f64, size_per_quote: f64) -> bool {
+    quote < state.quote
+        && !input
+            .filters
+            .size
+            .round_down_split(state.quote * size_per_quote)
+            .is_empty()
+}
+
+// Fees are paid in the bought base asset.
+fn buy_fills(filters: &Filters, price: f64, sizes: Vec<f64>, fee_rate: f64) -> Vec<Fill> {
+    sizes
+        .into_iter()
+        .map(|size| Fill {
+            price,
+            size,
+            quote: round_down(price * size, filters.quote_precision),
+            fee: round_half_up(size * fee_rate, filters.base_precision),
+        })
+        .collect()
+}
+
+// Fees are paid in the received quote asset.
+fn sell_fills(filters: &Filters, price: f64, sizes: Vec<f64>, fee_rate: f64) -> Vec<Fill> {
+    sizes
+        .into_iter()
+        .map(|size| {
+            let quote = round_down(price * size, filters.quote_precision);
+            Fill {
+                price,
+                size,
+                quote,
+                fee: round_half_up(quote * fee_rate, filters.quote_precision),
+            }
+        })
+        .collect()
+}
+
+// Fees are paid in base asset and therefore bought on top of the `sizes` repaid.
+fn buy_back_fills(filters: &Filters, price: f64, sizes: Vec<f64>, fee_rate: f64) -> Vec<Fill> {
+    sizes
+        .into_iter()
+        .map(|size| {
+            let fee = round_half_up(size * fee_rate, filters.base_precision);
+            let size = size + fee;
+            Fill {
+                price,
+                size,
+                quote: round_down(price * size, filters.quote_precision),
+                fee,
+            }
+        })
+        .collect()
+}
+
+// Asks the position sizer whether to add to or partially exit the open position, given the close
+// `price`. Futures positions are not scaled.
+fn scale(state: &mut State, price: f64, entries_allowed: bool) -> Option<Scale> {
+    let (change, available) = match &state.open_position {
+        Some(OpenPosition::Long(pos)) => (price / pos.fills[0].price - 1.0, state.quote),
+        // Quote not backing the loan is available.
+        Some(OpenPosition::Short(pos)) => {
+            (1.0 - price / pos.fills[0].price, state.quote - pos.assets())
+        }
+        _ => return None,
+    };
+
+    let fraction = f64::min(state.position_sizer.scale_out(change), 1.0);
+    if fraction > 0.0 {
+        return Some(Scale::Out(fraction));
+    }
+    if !entries_allowed {
+        return None;
+    }
+    let quote = f64::min(state.position_sizer.scale_in(available, change), available);
+    if quote > 0.0 {
+        Some(Scale::In(quote))
+    } else {
+        None
+    }
+}
+
+// Scaling orders are market orders. Ones which round down to zero size are dropped.
+fn scale_position(
+    state: &mut State,
+    input: &TradeInput,
+    candle: &Candle,
+    time: Timestamp,
+    price: f64,
+    scale: Scale,
+) {
+    let filters = input.filters;
+    let fee_rate = input.fees.taker;
+
+    match (&mut state.open_position, scale) {
+        (Some(OpenPosition::Long(pos)), Scale::In(quote)) => {
+            let price = state.slippage.buy_price(candle, price, quote / price);
+            let sizes = filters.size.round_down_split(quote / price);
+            if !sizes.is_empty() {
+                let fills = buy_fills(filters, price, sizes, fee_rate);
+                state.quote -= Fill::total_quote(&fills);
+                pos.scale_in(fills);
+            }
+        }
+        (Some(OpenPosition::Long(pos)), Scale::Out(fraction)) => {
+            let sizes = filters.size.round_down_split(pos.base_balance() * fraction);
+            if !sizes.is_empty() {
+                let price = state.slippage.sell_price(candle, price, sizes.iter().sum());
+                let fills = sell_fills(filters, price, sizes, fee_rate);
+                state.quote += Fill::total_quote(&fills) - Fill::total_fee(&fills);
+                pos.scale_out(fills);
+            }
+        }
+        (Some(OpenPosition::Short(pos)), Scale::In(collateral)) => {
+            let collateral_size: f64 = filters
+                .size
+                .round_down_split(collateral / price)
+                .iter()
+                .sum();
+            let borrowed = f64::min(
+                collateral_size * (state.margin_multiplier - 1) as f64,
+                input.borrow_info.limit - pos.outstanding(),
+            );
+            if borrowed > 0.0 {
+                let price = state.slippage.sell_price(candle, price, borrowed);
+                let fills = sell_fills(filters, price, filters.size.split(borrowed), fee_rate);
+                state.quote += Fill::total_quote(&fills) - Fill::total_fee(&fills);
+                pos.scale_in(collateral, borrowed, fills);
+            }
+        }
+        (Some(OpenPosition::Short(pos)), Scale::Out(fraction)) => {
+            // Interest accrued on the repaid part is settled along with it.
+            let repaid = pos.outstanding() * fraction;
+            let repay = repaid + interest(input, pos, time) * fraction;
+            let price = state.slippage.buy_price(candle, price, repay);
+            let fills = buy_back_fills(filters, price, filters.size.split(repay), fee_rate);
+            state.quote -= Fill::total_quote(&fills);
+            pos.scale_out(repaid, fills);
+        }
+        _ => {}
+    }
+}
+
+fn try_open_long_position(
+    state: &mut State,
+    input: &TradeInput,
+    time: Timestamp,
+    price: f64,
+    quote: f64,
+    fee_rate: f64,
+) -> Result<(), TradeError> {
+    let filters = input.filters;
+
+    // The position sizer chose to skip the entry.
+    if quote == 0.0 && state.quote > 0.0 {
+        return Ok(());
+    }
+
+    let sizes = filters.size.round_down_split(quote / price);
+    if sizes.is_empty() {
+        if sized_below_minimum(state, input, quote, 1.0 / price) {
+            return Ok(());
+        }
+        return Err(TradeError::SizeZero);
+    }
+
+    let fills = buy_fills(filters, price, sizes, fee_rate);
+    state.quote -= Fill::total_quote(&fills);
+    state.open_position = Some(OpenPosition::Long(OpenLongPosition::new(time, fills)));
+
+    Ok(())
+}
+
+fn close_long_position(
+    state: &mut State,
+    summary: &mut TradingSummary,
+    input: &TradeInput,
+    candle: &Candle,
+    time: Timestamp,
+    price: f64,
+    reason: CloseReason,
+) {
+    let filters = input.filters;
+
+    if let Some(OpenPosition::Long(pos)) = state.open_position.take() {
+        let sizes = filters.size.round_down_split(pos.base_balance());
+
+        let price = state.slippage.sell_price(candle, price, sizes.iter().sum());
+        let fills = sell_fills(filters, price, sizes, input.fees.taker);
+        state.quote += Fill::total_quote(&fills) - Fill::total_fee(&fills);
+
+        let pos = pos.close(time, fills, reason);
+        state.position_sizer.record(pos.profit() / pos.cost());
+        summary.record_event(TradeEvent::Close {
+            time,
+            price,
+            reason,
+            profit: pos.profit(),
+        });
+        summary.positions.push(Position::Long(pos));
+
+        state.open_position = None;
+    } else {
+        // TODO: Refactor to get rid of this.
+        panic!();
+    }
+}
+
+fn try_open_futures_position(
+    state: &mut State,
+    input: &TradeInput,
+    time: Timestamp,
+    advice: Advice,
+    price: f64,
+    margin: f64,
+    fee_rate: f64,
+) -> Result<(), TradeError> {
+    let filters = input.filters;
+
+    // The position sizer chose to skip the entry.
+    if margin == 0.0 && state.quote > 0.0 {
+        return Ok(());
+    }
+
+    // Fees are paid in quote on top of the margin, so both have to fit into the committed quote.
+    let leverage = state.margin_multiplier as f64;
+    let margin = margin / (1.0 + leverage * fee_rate);
+
+    let size_per_quote = leverage / (1.0 + leverage * fee_rate) / price;
+    let sizes = filters.size.round_down_split(margin * leverage / price);
+    if sizes.is_empty() {
+        if sized_below_minimum(state, input, margin, size_per_quote) {
+            return Ok(());
+        }
+        return Err(TradeError::SizeZero);
+    }
+
+    let fills: Vec<_> = sizes
+        .into_iter()
+        .map(|size| {
+            let quote = round_down(price * size, filters.quote_precision);
+            Fill {
+                price,
+                size,
+                quote,
+                fee: round_half_up(quote * fee_rate, filters.quote_precision),
+            }
+        })
+        .collect();
+
+    state.quote -= margin + Fill::total_fee(&fills);
+    state.open_position = Some(OpenPosition::Futures(OpenFuturesPosition::new(
+        time, advice, margin, fills,
+    )));
+
+    Ok(())
+}
+
+fn close_futures_position(
+    state: &mut State,
+    summary: &mut TradingSummary,
+    input: &TradeInput,
+    candle: &Candle,
+    time: Timestamp,
+    price: f64,
+    reason: CloseReason,
+) {
+    let filters = input.filters;
+
+    if let Some(OpenPosition::Futures(pos)) = state.open_position.take() {
+        let size = pos.size();
+        let price = match pos.advice {
+            Advice::Long => state.slippage.sell_price(candle, price, size),
+            _ => state.slippage.buy_price(candle, price, size),
+        };
+        let fills: Vec<_> = filters
+            .size
+            .split(size)
+            .into_iter()
+            .map(|size| {
+                let quote = round_down(price * size, filters.quote_precision);
+                Fill {
+                    price,
+                    size,
+                    quote,
+                    fee: round_half_up(quote * input.fees.taker, filters.quote_precision),
+                }
+            })
+            .collect();
+
+        let pos = pos.close(time, fills, reason);
+        state.quote += pos.gain();
+        state.position_sizer.record(pos.profit() / pos.cost());
+        summary.record_event(TradeEvent::Close {
+            time,
+            price,
+            reason,
+            profit: pos.profit(),
+        });
+        summary.positions.push(Position::Futures(pos));
+    } else {
+        panic!();
+    }
+}
+
+fn try_open_short_position(
+    state: &mut State,
+    input: &TradeInput,
+    candle: &Candle,
+    time: Timestamp,
+    price: f64,
+    collateral: f64,
+    fee_rate: f64,
+) -> Result<(), TradeError> {
+    if let Some(borrowed) = short_borrow_size(state, input, price, collateral)? {
+        let price = state.slippage.sell_price(candle, price, borrowed);
+        open_short_position(state, input, time, price, collateral, borrowed, fee_rate);
+    }
+    Ok(())
+}
+
+// Returns `None` if the position sizer chose to skip the entry.
+fn short_borrow_size(
+    state: &State,
+    input: &TradeInput,
+    price: f64,
+    collateral: f64,
+) -> Result<Option<f64>, TradeError> {
+    if collateral == 0.0 && state.quote > 0.0 {
+        return Ok(None);
+    }
+    let collateral_size: f64 = input
+        .filters
+        .size
+        .round_down_split(collateral / price)
+        .iter()
+        .sum();
+    if collateral_size == 0.0 {
+        if sized_below_minimum(state, input, collateral, 1.0 / price) {
+            return Ok(None);
+        }
+        return Err(TradeError::CollateralZero);
+    }
+    Ok(Some(f64::min(
+        collateral_size * (state.margin_multiplier - 1) as f64,
+        input.borrow_info.limit,
+    )))
+}
+
+fn open_short_position(
+    state: &mut State,
+    input: &TradeInput,
+    time: Timestamp,
+    price: f64,
+    collateral: f64,
+    borrowed: f64,
+    fee_rate: f64,
+) {
+    let filters = input.filters;
+
+    let fills = sell_fills(filters, price, filters.size.split(borrowed), fee_rate);
+    state.quote += Fill::total_quote(&fills) - Fill::total_fee(&fills);
+    state.open_position = Some(OpenPosition::Short(OpenShortPosition::new(
+        time, collateral, borrowed, fills,
+    )));
+}
+
+fn close_short_position(
+    state: &mut State,
+    summary: &mut TradingSummary,
+    input: &TradeInput,
+    candle: &Candle,
+    time: Timestamp,
+    price: f64,
+    reason: CloseReason,
+) {
+    let filters = input.filters;
+
+    if let Some(OpenPosition::Short(pos)) = state.open_position.take() {
+        let repay = pos.outstanding() + interest(input, &pos, time);
+        let price = state.slippage.buy_price(candle, price, repay);
+        let fills = buy_back_fills(filters, price, filters.size.split(repay), input.fees.taker);
+        state.quote -= Fill::total_quote(&fills);
+
+        let pos = pos.close(time, fills, reason);
+        state.position_sizer.record(pos.profit() / pos.cost());
+        summary.record_event(TradeEvent::Close {
+            time,
+            price,
+            reason,
+            profit: pos.profit(),
+        });
         summary.positions.push(Position::Short(pos));
 
         state.open_position = None;
-        state.quote -= quote;
     } else {
         panic!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trading::{
+        CooldownParams, FreshSignalParams, FuturesPosition, LongPosition, SameDirectionParams,
+        SessionFilterParams, ShortPosition,
+    };
+    use crate::{
+        candle_transform::CandleTransformParams,
+        filters::{Price, Size},
+        indicators::{MAParams, SmaParams},
+        position_sizer::{self, PositionSizerParams},
+        slippage,
+        statistics::CoreStatistics,
+        stop_loss,
+        strategies::{FourWeekRuleParams, SigParams, SignalParams, Strategy, StrategyParams},
+        take_profit,
+        time_exit::{self, TimeExitParams},
+        utils::MidTrendPolicy,
+    };
+
+    #[derive(Clone)]
+    struct Scripted {
+        advices: Vec<Advice>,
+        advice: Advice,
+        t: usize,
+    }
+
+    impl Strategy for Scripted {
+        fn maturity(&self) -> u32 {
+            1
+        }
+
+        fn mature(&self) -> bool {
+            true
+        }
+
+        fn update(&mut self, _candle: &Candle) {
+            self.advice = self.advices.get(self.t).copied().unwrap_or(Advice::None);
+            self.t += 1;
+        }
+    }
+
+    impl Signal for Scripted {
+        fn advice(&self) -> Advice {
+            self.advice
+        }
+    }
+
+    const FEES: Fees = Fees {
+        maker: 0.0,
+        taker: 0.0,
+    };
+    const FILTERS: Filters = Filters {
+        price: Price {
+            min: 0.0,
+            max: 0.0,
+            step: 0.0,
+        },
+        size: Size {
+            min: 0.0,
+            max: 0.0,
+            step: 0.0,
+        },
+        base_precision: 8,
+        quote_precision: 8,
+    };
+    const BORROW_INFO: BorrowInfo = BorrowInfo {
+        interest_interval: 1,
+        interest_rate: 0.0,
+        limit: 1.0,
+    };
+
+    const TRADER: TraderParams = TraderParams {
+        interval: Interval(1),
+        direction: Direction::Both,
+        margin_multiplier: 2,
+        re_entry: ReEntryParams::FreshSignal(FreshSignalParams {}),
+        session: SessionFilterParams {
+            start_hour: 0,
+            end_hour: 0,
+            weekdays: 0b111_1111,
+        },
+    };
+
+    fn candle(time: u64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            time: time.into(),
+            open,
+            high,
+            low,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    fn input(candles: &[Candle]) -> TradeInput<'_> {
+        TradeInput {
+            candles,
+            fees: &FEES,
+            filters: &FILTERS,
+            borrow_info: &BORROW_INFO,
+            maintenance_margin_level: 1.1,
+            quote: 1.0,
+            exit_trigger: ExitTrigger::Close,
+            execution_timing: ExecutionTiming::Close,
+            entry_order: EntryOrder::Market,
+            slippage: SlippageParams::default(),
+            market: Market::Spot,
+            funding_rates: &[],
+            blackouts: &[],
+            gap_policy: GapPolicy::default(),
+            record: false,
+        }
+    }
+
+    // Stop-loss is hit at -10% and take-profit at +20% from the entry candle's close.
+    fn run(advices: &[Advice], input: &TradeInput) -> TradingSummary {
+        run_with(advices, input, &TRADER, PositionSizerParams::default())
+    }
+
+    fn run_with(
+        advices: &[Advice],
+        input: &TradeInput,
+        trader: &TraderParams,
+        position_sizer: PositionSizerParams,
+    ) -> TradingSummary {
+        let mut summary = TradingSummary::new(0.into(), (input.candles.len() as u64).into(), 1.0);
+        if input.record {
+            summary.recording = Some(Recording::default());
+        }
+        let mut state = State::new(
+            input.quote,
+            trader,
+            Box::new(Scripted {
+                advices: advices.to_vec(),
+                advice: Advice::None,
+                t: 0,
+            }),
+            stop_loss::StopLossParams::Basic(stop_loss::BasicParams {
+                up_threshold: 0.1,
+                down_threshold: 0.1,
+            })
+            .construct(),
+            take_profit::TakeProfitParams::Basic(take_profit::BasicParams {
+                up_threshold: 0.2,
+                down_threshold: 0.2,
+            })
+            .construct(),
+            TimeExitParams::default().construct(),
+            input.slippage.construct(),
+            position_sizer.construct(input.quote),
+            CandleTransformParams::default().construct(),
+        );
+        for candle in input.candles {
+            tick(&mut state, &mut summary, input, 1.into(), candle).unwrap();
+        }
+        summary
+    }
+
+    fn closed_long(summary: &TradingSummary) -> &LongPosition {
+        match &summary.positions[..] {
+            [Position::Long(pos)] => pos,
+            _ => panic!("expected a single closed long position"),
+        }
+    }
+
+    fn closed_short(summary: &TradingSummary) -> &ShortPosition {
+        match &summary.positions[..] {
+            [Position::Short(pos)] => pos,
+            _ => panic!("expected a single closed short position"),
+        }
+    }
+
+    fn closed_futures(summary: &TradingSummary) -> &FuturesPosition {
+        match &summary.positions[..] {
+            [Position::Futures(pos)] => pos,
+            _ => panic!("expected a single closed futures position"),
+        }
+    }
+
+    fn close_price_reason(pos: &LongPosition) -> (f64, CloseReason) {
+        (pos.close_fills[0].price, pos.close_reason)
+    }
+
+    fn high_low(priority: ExitPriority) -> ExitTrigger {
+        ExitTrigger::HighLow { priority }
+    }
+
+    #[test]
+    fn test_close_trigger_ignores_wick() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 10.5, 8.5, 10.0),
+        ];
+        let summary = run(&[Advice::Long], &input(&candles));
+        assert!(summary.positions.is_empty());
+    }
+
+    #[test]
+    fn test_high_low_trigger_fills_stop_loss_at_level() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 10.5, 8.5, 10.0),
+        ];
+        let summary = run(
+            &[Advice::Long],
+            &TradeInput {
+                exit_trigger: high_low(ExitPriority::StopLoss),
+                ..input(&candles)
+            },
+        );
+        assert_eq!(
+            close_price_reason(closed_long(&summary)),
+            (9.0, CloseReason::StopLoss)
+        );
+    }
+
+    #[test]
+    fn test_high_low_trigger_fills_at_open_on_gap() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 8.0, 8.5, 7.5, 8.0),
+        ];
+        let summary = run(
+            &[Advice::Long],
+            &TradeInput {
+                exit_trigger: high_low(ExitPriority::TakeProfit),
+                ..input(&candles)
+            },
+        );
+        assert_eq!(
+            close_price_reason(closed_long(&summary)),
+            (8.0, CloseReason::StopLoss)
+        );
+    }
+
+    #[test]
+    fn test_high_low_trigger_priority_when_both_hit() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 11.5, 12.5, 8.5, 10.0),
+        ];
+        let expectations = [
+            (ExitPriority::StopLoss, (9.0, CloseReason::StopLoss)),
+            (ExitPriority::TakeProfit, (12.0, CloseReason::TakeProfit)),
+            (ExitPriority::NearestToOpen, (12.0, CloseReason::TakeProfit)),
+        ];
+        for (priority, expected) in expectations {
+            let summary = run(
+                &[Advice::Long],
+                &TradeInput {
+                    exit_trigger: high_low(priority),
+                    ..input(&candles)
+                },
+            );
+            assert_eq!(close_price_reason(closed_long(&summary)), expected);
+        }
+    }
+
+    #[test]
+    fn test_high_low_trigger_short_stop_loss() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 11.5, 9.5, 10.0),
+        ];
+        let summary = run(
+            &[Advice::Short],
+            &TradeInput {
+                exit_trigger: high_low(ExitPriority::StopLoss),
+                ..input(&candles)
+            },
+        );
+        let pos = closed_short(&summary);
+        assert_eq!(pos.close_fills[0].price, 11.0);
+        assert_eq!(pos.close_reason, CloseReason::StopLoss);
+    }
+
+    #[test]
+    fn test_next_open_execution_fills_at_next_candle_open() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 11.0, 11.5, 11.0, 11.5),
+            candle(2, 11.5, 11.8, 11.5, 11.8),
+            candle(3, 11.9, 11.9, 11.9, 11.9),
+        ];
+        let summary = run(
+            &[Advice::Long, Advice::None, Advice::Liquidate],
+            &TradeInput {
+                execution_timing: ExecutionTiming::NextOpen,
+                ..input(&candles)
+            },
+        );
+        let pos = closed_long(&summary);
+        assert_eq!(pos.open_time, 1);
+        assert_eq!(pos.open_fills[0].price, 11.0);
+        assert_eq!(pos.close_time, 3);
+        assert_eq!(pos.close_fills[0].price, 11.9);
+        assert_eq!(pos.close_reason, CloseReason::Strategy);
+    }
+
+    #[test]
+    fn test_next_open_execution_drops_action_pending_on_last_candle() {
+        let candles = [candle(0, 10.0, 10.0, 10.0, 10.0)];
+        let summary = run(
+            &[Advice::Long],
+            &TradeInput {
+                execution_timing: ExecutionTiming::NextOpen,
+                ..input(&candles)
+            },
+        );
+        assert!(summary.positions.is_empty());
+    }
+
+    #[test]
+    fn test_slippage_applied_against_position() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 10.0, 10.0, 10.0),
+        ];
+        let summary = run(
+            &[Advice::Long, Advice::Liquidate],
+            &TradeInput {
+                slippage: SlippageParams::Fixed(slippage::FixedParams { bps: 100.0 }),
+                ..input(&candles)
+            },
+        );
+        let pos = closed_long(&summary);
+        assert_eq!(pos.open_fills[0].price, 10.1);
+        assert_eq!(pos.close_fills[0].price, 9.9);
+        assert!(pos.profit() < 0.0);
+    }
+
+    #[test]
+    fn test_limit_entry_fills_as_maker_when_reached() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 6.0, 6.0, 6.0, 6.0),
+            candle(2, 6.0, 6.0, 4.0, 5.5),
+            candle(3, 5.5, 5.5, 5.5, 5.5),
+        ];
+        let summary = run(
+            &[Advice::Long, Advice::None, Advice::None, Advice::Liquidate],
+            &TradeInput {
+                fees: &Fees {
+                    maker: 0.001,
+                    taker: 0.0,
+                },
+                quote: 5.0,
+                entry_order: EntryOrder::Limit {
+                    offset: 0.5,
+                    timeout: 2,
+                },
+                ..input(&candles)
+            },
+        );
+        let pos = closed_long(&summary);
+        assert_eq!(pos.open_time, 3);
+        assert_eq!(pos.open_fills[0].price, 5.0);
+        assert_eq!(pos.open_fills[0].size, 1.0);
+        assert_eq!(pos.open_fills[0].fee, 0.001);
+        assert!(summary.expired_orders.is_empty());
+    }
+
+    #[test]
+    fn test_limit_entry_expires_after_timeout() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 6.0, 6.0, 6.0, 6.0),
+            candle(2, 6.0, 6.0, 6.0, 6.0),
+            candle(3, 4.0, 4.0, 4.0, 4.0),
+        ];
+        let summary = run(
+            &[Advice::Long],
+            &TradeInput {
+                entry_order: EntryOrder::Limit {
+                    offset: 0.5,
+                    timeout: 2,
+                },
+                ..input(&candles)
+            },
+        );
+        assert!(summary.positions.is_empty());
+        assert_eq!(
+            summary.expired_orders,
+            [ExpiredOrder {
+                advice: Advice::Long,
+                price: 5.0,
+                time: 1.into(),
+                expire_time: 3.into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_limit_entry_cancelled_on_opposite_advice() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 6.0, 6.0, 6.0, 6.0),
+            candle(2, 6.0, 6.0, 6.0, 6.0),
+        ];
+        let summary = run(
+            &[Advice::Long, Advice::Short],
+            &TradeInput {
+                entry_order: EntryOrder::Limit {
+                    offset: 0.5,
+                    timeout: 2,
+                },
+                ..input(&candles)
+            },
+        );
+        assert!(summary.positions.is_empty());
+        assert!(summary.expired_orders.is_empty());
+        assert_eq!(
+            summary.cancelled_orders,
+            [CancelledOrder {
+                advice: Advice::Long,
+                price: 5.0,
+                time: 1.into(),
+                cancel_time: 2.into(),
+                reason: CancelReason::Strategy,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_position_sizer_limits_committed_quote() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 10.0, 10.0, 10.0),
+            candle(2, 10.0, 10.0, 10.0, 10.0),
+        ];
+        let summary = run_with(
+            &[Advice::Long, Advice::Short, Advice::Liquidate],
+            &TradeInput {
+                quote: 10.0,
+                ..input(&candles)
+            },
+            &TRADER,
+            PositionSizerParams::FixedFraction(position_sizer::FixedFractionParams {
+                fraction: 0.5,
+            }),
+        );
+        match &summary.positions[..] {
+            [Position::Long(long), Position::Short(short)] => {
+                assert_eq!(long.cost(), 5.0);
+                assert_eq!(short.collateral, 5.0);
+                assert_eq!(short.borrowed, 0.5);
+            }
+            _ => panic!("expected a closed long followed by a closed short position"),
+        }
+    }
+
+    #[test]
+    fn test_position_sizer_below_minimum_size_skips_entry() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 10.0, 10.0, 10.0),
+        ];
+        let filters = Filters {
+            size: Size {
+                min: 1.0,
+                max: 0.0,
+                step: 1.0,
+            },
+            ..FILTERS
+        };
+        let summary = run_with(
+            &[Advice::Long, Advice::Short],
+            &TradeInput {
+                quote: 10.0,
+                filters: &filters,
+                ..input(&candles)
+            },
+            &TRADER,
+            PositionSizerParams::FixedFraction(position_sizer::FixedFractionParams {
+                fraction: 0.05,
+            }),
+        );
+        // Would have halted with `TradeError::SizeZero` in `run_with`.
+        assert!(summary.positions.is_empty());
+    }
+
+    #[test]
+    fn test_position_sizer_scales_long_in_and_out() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.5, 10.5, 10.5, 10.5),
+            candle(2, 11.0, 11.0, 11.0, 11.0),
+            candle(3, 11.0, 11.0, 11.0, 11.0),
+        ];
+        let summary = run_with(
+            &[Advice::Long, Advice::None, Advice::None, Advice::Liquidate],
+            &TradeInput {
+                quote: 84.0,
+                ..input(&candles)
+            },
+            &TRADER,
+            PositionSizerParams::Pyramid(position_sizer::PyramidParams {
+                fraction: 0.5,
+                step: 0.05,
+                adds: 1,
+                take: 0.5,
+            }),
+        );
+        let pos = closed_long(&summary);
+        // Entered with half of the quote and added half of the rest after a 5% move.
+        assert_eq!(Fill::total_quote(&pos.open_fills), 63.0);
+        assert_eq!(pos.open_fills[1].size, 2.0);
+        // Exited half of the position after a 10% move and the rest on the strategy's exit.
+        assert_eq!(pos.close_fills.len(), 2);
+        assert!(f64::abs(pos.close_fills[0].size - 3.1) < 1e-9);
+        assert!(f64::abs(Fill::total_size(&pos.close_fills) - 6.2) < 1e-9);
+    }
+
+    #[test]
+    fn test_position_sizer_scales_short_in_and_out() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 9.5, 9.5, 9.5, 9.5),
+            candle(2, 8.9, 8.9, 8.9, 8.9),
+            candle(3, 8.9, 8.9, 8.9, 8.9),
+        ];
+        let summary = run_with(
+            &[Advice::Short, Advice::None, Advice::None, Advice::Liquidate],
+            &TradeInput {
+                quote: 40.0,
+                borrow_info: &BorrowInfo {
+                    limit: 100.0,
+                    ..BORROW_INFO
+                },
+                ..input(&candles)
+            },
+            &TRADER,
+            PositionSizerParams::Pyramid(position_sizer::PyramidParams {
+                fraction: 0.5,
+                step: 0.05,
+                adds: 1,
+                take: 0.5,
+            }),
+        );
+        let pos = closed_short(&summary);
+        assert_eq!(pos.collateral, 30.0);
+        assert_eq!(pos.borrowed, 2.0 + 10.0 / 9.5);
+        assert_eq!(pos.open_fills.len(), 2);
+        assert_eq!(pos.close_fills.len(), 2);
+        assert!(f64::abs(Fill::total_size(&pos.close_fills) - pos.borrowed) < 1e-9);
+        assert!(pos.profit() > 0.0);
+    }
+
+    #[test]
+    fn test_orders_split_when_size_filter_caps_them() {
+        let candles = [candle(0, 1.0, 1.0, 1.0, 1.0), candle(1, 2.0, 2.0, 2.0, 2.0)];
+        let summary = run(
+            &[Advice::Long, Advice::Liquidate],
+            &TradeInput {
+                filters: &Filters {
+                    size: Size {
+                        min: 0.0,
+                        max: 0.5,
+                        step: 0.0,
+                    },
+                    ..FILTERS
+                },
+                quote: 1.5,
+                ..input(&candles)
+            },
+        );
+        let pos = closed_long(&summary);
+        assert_eq!(pos.open_fills.len(), 3);
+        assert_eq!(pos.close_fills.len(), 3);
+        assert_eq!(pos.base_gain(), 1.5);
+        assert_eq!(pos.gain(), 3.0);
+        assert_eq!(pos.profit(), 1.5);
+    }
+
+    #[test]
+    fn test_short_liquidated_when_high_reaches_maintenance_margin() {
+        // Assets are 1.0 collateral and 1.0 from selling 0.1 borrowed at 10.0. With a maintenance
+        // margin level of 1.1 the position is liquidated once the price reaches 2.0 / 0.11.
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 17.0, 10.0, 10.0),
+            candle(2, 10.0, 19.0, 10.0, 12.0),
+        ];
+        let summary = run(&[Advice::Short], &input(&candles));
+        let pos = closed_short(&summary);
+        assert_eq!(pos.close_time, 3);
+        assert!(f64::abs(pos.close_fills[0].price - 2.0 / 0.11) < 1e-9);
+        assert_eq!(pos.close_reason, CloseReason::Liquidation);
+    }
+
+    #[test]
+    fn test_direction_restricts_entries() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 10.0, 10.0, 10.0),
+            candle(2, 10.0, 10.0, 10.0, 10.0),
+        ];
+        let advices = [Advice::Short, Advice::Long, Advice::Liquidate];
+
+        let summary = run_with(
+            &advices,
+            &input(&candles),
+            &TraderParams {
+                direction: Direction::Long,
+                ..TRADER
+            },
+            PositionSizerParams::default(),
+        );
+        closed_long(&summary);
+
+        let summary = run_with(
+            &advices,
+            &input(&candles),
+            &TraderParams {
+                direction: Direction::Short,
+                ..TRADER
+            },
+            PositionSizerParams::default(),
+        );
+        closed_short(&summary);
+    }
+
+    #[test]
+    fn test_recording_tracks_equity_and_events() {
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 9.5, 8.0, 9.0),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let summary = run(
+            &[Advice::Long, Advice::None, Advice::Liquidate],
+            &TradeInput {
+                record: true,
+                ..input(&candles)
+            },
+        );
+        let recording = summary.recording.unwrap();
+        assert_eq!(
+            recording
+                .candles
+                .iter()
+                .map(|record| (record.advice, record.equity))
+                .collect::<Vec<_>>(),
+            [
+                (Advice::Long, 1.0),
+                (Advice::None, 1.125),
+                (Advice::Liquidate, 1.125)
+            ]
+        );
+        assert_eq!(
+            recording.events,
+            [
+                TradeEvent::Open {
+                    time: 1.into(),
+                    advice: Advice::Long,
+                    price: 8.0,
+                },
+                TradeEvent::Close {
+                    time: 3.into(),
+                    price: 9.0,
+                    reason: CloseReason::Strategy,
+                    profit: 0.125,
+                },
+            ]
+        );
+    }
+
+    // Goes long when the close is at or above the previous close and short when below.
+    fn breakout_params() -> TradingParams {
+        TradingParams {
+            strategy: StrategyParams::FourWeekRule(FourWeekRuleParams {
+                period: 1,
+                ma: MAParams::Sma(SmaParams { period: 1 }),
+            }),
+            trader: TRADER,
+            stop_loss: stop_loss::StopLossParams::Noop(stop_loss::NoopParams {}),
+            take_profit: take_profit::TakeProfitParams::Noop(take_profit::NoopParams {}),
+            time_exit: TimeExitParams::default(),
+            position_sizer: PositionSizerParams::default(),
+            candle_transform: CandleTransformParams::default(),
+        }
+    }
+
+    #[test]
+    fn test_re_entry_after_stop_loss() {
+        let candles = [
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(1, 10.0, 10.0, 8.5, 8.5),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+            candle(3, 9.0, 9.0, 9.0, 9.0),
+            candle(4, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let advices = [
+            Advice::Long,
+            Advice::None,
+            Advice::None,
+            Advice::None,
+            Advice::Short,
+        ];
+        let open_times = |re_entry| {
+            let trader = TraderParams {
+                direction: Direction::Long,
+                re_entry,
+                ..TRADER
+            };
+            let summary = run_with(
+                &advices,
+                &input(&candles),
+                &trader,
+                PositionSizerParams::default(),
+            );
+            summary
+                .positions
+                .iter()
+                .map(|pos| match pos {
+                    Position::Long(pos) => pos.open_time.0,
+                    _ => panic!("expected long positions"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            open_times(ReEntryParams::FreshSignal(FreshSignalParams {})),
+            [1]
+        );
+        assert_eq!(
+            open_times(ReEntryParams::SameDirection(SameDirectionParams {})),
+            [1, 3]
+        );
+        assert_eq!(
+            open_times(ReEntryParams::Cooldown(CooldownParams { candles: 2 })),
+            [1, 4]
+        );
+    }
+
+    #[test]
+    fn test_blackout_flattens_and_blocks_entries() {
+        let candles: Vec<_> = (0..4).map(|i| candle(i, 8.0, 8.0, 8.0, 8.0)).collect();
+        let blackouts = [Blackout {
+            start: 2.into(),
+            end: 3.into(),
+            flatten: true,
+        }];
+        let summary = run(
+            &[Advice::Long, Advice::None, Advice::Short, Advice::Long],
+            &TradeInput {
+                blackouts: &blackouts,
+                record: true,
+                ..input(&candles)
+            },
+        );
+
+        let pos = closed_long(&summary);
+        assert_eq!(pos.close_time, 2);
+        assert_eq!(pos.close_reason, CloseReason::Blackout);
+        // The short signal falls within the blackout and is skipped.
+        let open_times: Vec<_> = summary
+            .recording
+            .unwrap()
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TradeEvent::Open { time, advice, .. } => Some((time.0, *advice)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(open_times, [(1, Advice::Long), (4, Advice::Long)]);
+    }
+
+    #[test]
+    fn test_trader_restores_snapshot() {
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 8.0, 8.0, 8.0),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let input = input(&candles);
+        let params = breakout_params();
+        let mut trader = Trader::new(&params, &input);
+        trader.tick(&candles[0]).unwrap();
+        trader.tick(&candles[1]).unwrap();
+        let snapshot = trader.snapshot();
+
+        // Price breaks down and the long position is reversed into a short.
+        trader.tick(&candle(2, 5.0, 5.0, 5.0, 5.0)).unwrap();
+        assert!(matches!(
+            trader.open_position(),
+            Some(OpenPosition::Short(_))
+        ));
+        assert_eq!(trader.summary().positions.len(), 1);
+
+        trader.restore(snapshot);
+        trader.tick(&candles[2]).unwrap();
+        assert!(matches!(
+            trader.open_position(),
+            Some(OpenPosition::Long(_))
+        ));
+        assert!(trader.summary().positions.is_empty());
+        assert_eq!(trader.finish().end, 3);
+    }
+
+    #[test]
+    fn test_trade_records_halt() {
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 8.0, 8.0, 8.0),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let input = TradeInput {
+            quote: 0.0,
+            ..input(&candles)
+        };
+        let params = breakout_params();
+
+        let mut trader = Trader::new(&params, &input);
+        trader.tick(&candles[0]).unwrap();
+        assert_eq!(trader.tick(&candles[1]), Err(TradeError::SizeZero));
+        assert_eq!(trader.tick(&candles[2]), Err(TradeError::SizeZero));
+
+        let summary = trade(&params, &input);
+        assert_eq!(
+            summary.halted,
+            Some(Halt {
+                time: 1.into(),
+                error: TradeError::SizeZero,
+            })
+        );
+        assert_eq!(summary.end, 3);
+    }
+
+    #[test]
+    fn test_time_exits() {
+        let run = |closes: &[f64], time_exit| {
+            let candles: Vec<_> = closes
+                .iter()
+                .enumerate()
+                .map(|(i, &close)| candle(i as u64, close, close, close, close))
+                .collect();
+            trade(
+                &TradingParams {
+                    time_exit,
+                    ..breakout_params()
+                },
+                &input(&candles),
+            )
+        };
+
+        // Entered on the second candle.
+        let summary = run(
+            &[8.0, 8.0, 8.0, 8.0, 8.0, 8.0],
+            TimeExitParams::MaxHolding(time_exit::MaxHoldingParams { candles: 3 }),
+        );
+        let pos = closed_long(&summary);
+        assert_eq!(
+            (pos.close_time.0, pos.close_reason),
+            (5, CloseReason::TimeExit)
+        );
+        assert_eq!(CoreStatistics::compose(&summary).num_time_exits, 1);
+
+        let summary = run(
+            &[8.0, 8.0, 9.0, 9.0, 9.0, 9.0],
+            TimeExitParams::NoNewHigh(time_exit::NoNewHighParams { candles: 2 }),
+        );
+        let pos = closed_long(&summary);
+        assert_eq!(
+            (pos.close_time.0, pos.close_reason),
+            (5, CloseReason::TimeExit)
+        );
+
+        let not_in_profit =
+            TimeExitParams::NotInProfit(time_exit::NotInProfitParams { candles: 2 });
+        let summary = run(&[8.0, 8.0, 8.0, 8.0, 8.0, 8.0], not_in_profit);
+        let pos = closed_long(&summary);
+        assert_eq!(
+            (pos.close_time.0, pos.close_reason),
+            (4, CloseReason::TimeExit)
+        );
+        let summary = run(&[8.0, 8.0, 9.0, 9.0, 9.0, 9.0], not_in_profit);
+        assert_eq!(closed_long(&summary).close_reason, CloseReason::Cancelled);
+    }
+
+    #[test]
+    fn test_recording_traces_debug_values() {
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 8.0, 8.0, 8.0),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let summary = trade(
+            &breakout_params(),
+            &TradeInput {
+                record: true,
+                ..input(&candles)
+            },
+        );
+
+        let trace = summary.recording.unwrap().trace;
+        let mut names: Vec<_> = trace.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        // Noop stop-loss and take-profit have no levels to trace.
+        assert_eq!(
+            names,
+            ["strategy.highest", "strategy.lowest", "strategy.ma.value"]
+        );
+        let values = |name: &str| -> Vec<_> {
+            trace[name]
+                .iter()
+                .map(|point| (point.time.0, point.value))
+                .collect()
+        };
+        assert_eq!(values("strategy.ma.value"), [(0, 8.0), (1, 8.0), (2, 9.0)]);
+        // The channel includes the latest close, ready for the next candle to break out of.
+        assert_eq!(values("strategy.highest"), [(0, 8.0), (1, 8.0), (2, 9.0)]);
+    }
+
+    #[test]
+    fn test_gap_policies() {
+        // Candles 3 and 4 are missing while a long position is open.
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 8.0, 8.0, 8.0),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+            candle(5, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let params = breakout_params();
+        let run = |gap_policy| {
+            trade(
+                &params,
+                &TradeInput {
+                    gap_policy,
+                    record: true,
+                    ..input(&candles)
+                },
+            )
+        };
+
+        let summary = run(GapPolicy::Skip);
+        assert_eq!((summary.num_gaps, summary.num_missing_candles), (1, 2));
+        assert_eq!(summary.halted, None);
+        assert_eq!(summary.recording.unwrap().candles.len(), 4);
+
+        let summary = run(GapPolicy::ForwardFill);
+        assert_eq!((summary.num_gaps, summary.num_missing_candles), (1, 2));
+        assert_eq!(summary.halted, None);
+        let times: Vec<_> = summary
+            .recording
+            .unwrap()
+            .candles
+            .iter()
+            .map(|record| record.time.0)
+            .collect();
+        assert_eq!(times, [0, 1, 2, 3, 4, 5]);
+
+        let summary = run(GapPolicy::Halt);
+        assert_eq!(
+            summary.halted,
+            Some(Halt {
+                time: 5.into(),
+                error: TradeError::Gap,
+            })
+        );
+        let pos = closed_long(&summary);
+        assert_eq!(pos.close_time, 3);
+        assert_eq!(close_price_reason(pos), (9.0, CloseReason::Cancelled));
+    }
+
+    #[test]
+    fn test_out_of_order_candles_rejected() {
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 8.0, 8.0, 8.0),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let input = TradeInput {
+            record: true,
+            ..input(&candles)
+        };
+        let mut trader = Trader::new(&breakout_params(), &input);
+        trader.tick(&candles[0]).unwrap();
+        trader.tick(&candles[1]).unwrap();
+        // Re-sent and late candles are rejected without halting.
+        assert_eq!(trader.tick(&candles[1]), Err(TradeError::OutOfOrder));
+        assert_eq!(trader.tick(&candles[0]), Err(TradeError::OutOfOrder));
+        trader.tick(&candles[2]).unwrap();
+
+        let summary = trader.finish();
+        assert_eq!(summary.halted, None);
+        assert_eq!(summary.num_gaps, 0);
+        assert_eq!(summary.end, 3);
+        let times: Vec<_> = summary
+            .recording
+            .unwrap()
+            .candles
+            .iter()
+            .map(|record| record.time.0)
+            .collect();
+        assert_eq!(times, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_skipped_gap_fed_to_buffered_strategy() {
+        // Buffering into pairs of candles cannot deal with a gap which ends on the last candle of a
+        // pair.
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(3, 9.0, 9.0, 9.0, 9.0),
+            candle(4, 9.0, 9.0, 9.0, 9.0),
+            candle(5, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let params = TradingParams {
+            strategy: StrategyParams::Sig(SigParams {
+                sig: SignalParams::FourWeekRule(FourWeekRuleParams {
+                    period: 1,
+                    ma: MAParams::Sma(SmaParams { period: 1 }),
+                }),
+                persistence: 0,
+                mid_trend_policy: MidTrendPolicy::Ignore,
+                buffer_interval: Some(2.into()),
+            }),
+            ..breakout_params()
+        };
+        let summary = trade(&params, &input(&candles));
+        assert_eq!((summary.num_gaps, summary.num_missing_candles), (1, 2));
+        assert_eq!(summary.halted, None);
+    }
+
+    #[test]
+    fn test_futures_long_pays_funding() {
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 8.0, 8.0, 8.0),
+            candle(2, 9.0, 9.0, 9.0, 9.0),
+        ];
+        let summary = run(
+            &[Advice::Long, Advice::None, Advice::Liquidate],
+            &TradeInput {
+                market: Market::Futures {
+                    maintenance_margin_rate: 0.01,
+                },
+                funding_rates: &[FundingRate {
+                    time: 1.into(),
+                    rate: 0.01,
+                }],
+                ..input(&candles)
+            },
+        );
+        let pos = closed_futures(&summary);
+        assert_eq!(pos.open_fills[0].size, 0.25);
+        assert_eq!(pos.funding, 0.02);
+        assert!(f64::abs(pos.profit() - 0.23) < 1e-9);
+    }
+
+    #[test]
+    fn test_futures_short_liquidated_at_isolated_margin() {
+        // Margin of 1.0 backs 0.25 sold at 8.0. The remaining margin meets 10% of the notional
+        // value at 3.0 / 0.275.
+        let candles = [
+            candle(0, 8.0, 8.0, 8.0, 8.0),
+            candle(1, 8.0, 11.0, 8.0, 8.0),
+        ];
+        let summary = run(
+            &[Advice::Short],
+            &TradeInput {
+                market: Market::Futures {
+                    maintenance_margin_rate: 0.1,
+                },
+                ..input(&candles)
+            },
+        );
+        let pos = closed_futures(&summary);
+        let price = pos.close_fills[0].price;
+        assert!(f64::abs(price - 3.0 / 0.275) < 1e-9);
+        assert_eq!(pos.close_reason, CloseReason::Liquidation);
+        assert!(f64::abs(pos.gain() - 0.1 * 0.25 * price) < 1e-6);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    indicators::{Atr, MAParams},
+    Candle,
+};
+
+// Levels `multiplier` average true ranges above and below the entry close, with the average true
+// range fixed as of entry. Absent for positions entered before the average true range has matured.
+#[derive(Clone)]
+pub struct AtrBand {
+    atr: Atr,
+    multiplier: f64,
+    close_at_position: f64,
+    distance: Option<f64>,
+}
+
+impl AtrBand {
+    pub fn new(ma: &MAParams, multiplier: f64) -> Self {
+        Self {
+            atr: Atr::new(ma),
+            multiplier,
+            close_at_position: 0.0,
+            distance: None,
+        }
+    }
+
+    pub fn upper(&self) -> Option<f64> {
+        self.distance
+            .map(|distance| self.close_at_position + distance)
+    }
+
+    pub fn lower(&self) -> Option<f64> {
+        self.distance
+            .map(|distance| self.close_at_position - distance)
+    }
+
+    pub fn clear(&mut self, candle: &Candle) {
+        self.close_at_position = candle.close;
+        self.distance = if self.atr.mature() {
+            Some(self.multiplier * self.atr.value)
+        } else {
+            None
+        };
+    }
+
+    pub fn update(&mut self, candle: &Candle) {
+        self.atr.update(candle.high, candle.low, candle.close);
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn hlc_candle(high: f64, low: f64, close: f64) -> Candle {
+    Candle {
+        time: 0.into(),
+        open: close,
+        high,
+        low,
+        close,
+        volume: 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SmaParams;
+
+    #[test]
+    fn test_levels_fixed_at_entry() {
+        let mut band = AtrBand::new(&MAParams::Sma(SmaParams { period: 2 }), 2.0);
+        let entry = hlc_candle(11.0, 9.0, 10.0);
+        band.update(&entry);
+        band.clear(&entry);
+        // Absent until the average true range has matured.
+        assert_eq!(band.upper(), None);
+        assert_eq!(band.lower(), None);
+
+        // Average true range of 2.5.
+        let entry = hlc_candle(13.0, 12.0, 12.5);
+        band.update(&entry);
+        band.clear(&entry);
+        assert_eq!(band.upper(), Some(17.5));
+        assert_eq!(band.lower(), Some(7.5));
+
+        // The average true range narrows to 2.25 but the levels stay put.
+        band.update(&hlc_candle(12.0, 11.0, 11.5));
+        assert_eq!(band.upper(), Some(17.5));
+        assert_eq!(band.lower(), Some(7.5));
+    }
+}
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 
 use crate::{Candle, Interval};
 
+#[derive(Clone)]
 pub struct BufferedCandle {
     interval: Interval,
     buffer_interval: Interval,
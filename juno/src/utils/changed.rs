@@ -1,5 +1,6 @@
 use crate::Advice;
 
+#[derive(Clone)]
 pub struct Changed {
     enabled: bool,
     previous: Advice,
@@ -16,6 +16,7 @@ const MID_TREND_POLICY_CHOICES: [MidTrendPolicy; 3] = [
     MidTrendPolicy::Ignore,
 ];
 
+#[derive(Clone)]
 pub struct MidTrend {
     policy: MidTrendPolicy,
     previous: Option<Advice>,
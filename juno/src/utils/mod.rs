@@ -1,8 +1,10 @@
+mod atr_band;
 mod buffered_candle;
 mod changed;
 mod mid_trend;
 mod persistence;
 
+pub use atr_band::*;
 pub use buffered_candle::*;
 pub use changed::*;
 pub use mid_trend::*;
@@ -10,6 +12,29 @@ pub use persistence::*;
 
 use crate::Advice;
 
+// Makes `Box<dyn $trait>` cloneable. `$clone_trait` has to be declared as a supertrait of
+// `$trait` and is implemented for every implementor which is `Clone`.
+macro_rules! box_clone {
+    ($trait:ident, $clone_trait:ident) => {
+        pub trait $clone_trait {
+            fn clone_box(&self) -> Box<dyn $trait>;
+        }
+
+        impl<T: $trait + Clone + 'static> $clone_trait for T {
+            fn clone_box(&self) -> Box<dyn $trait> {
+                Box::new(self.clone())
+            }
+        }
+
+        impl Clone for Box<dyn $trait> {
+            fn clone(&self) -> Self {
+                self.clone_box()
+            }
+        }
+    };
+}
+pub(crate) use box_clone;
+
 pub fn combine(advice1: Advice, advice2: Advice) -> Advice {
     if advice1 == Advice::None || advice2 == Advice::None {
         Advice::None
@@ -2,6 +2,7 @@ use std::cmp::min;
 
 use crate::Advice;
 
+#[derive(Clone)]
 pub struct Persistence {
     age: u32,
     level: u32,
@@ -234,6 +234,7 @@ fn test_derive_context_mutate() {
 
 // There are not tests for this struct. It is simply checking if the Chromosome derive macro is
 // able to generate a context in case of a field with #[serde(default)].
+#[allow(dead_code)]
 #[derive(Chromosome, Clone, Serialize)]
 struct WithDefault {
     #[serde(default)]
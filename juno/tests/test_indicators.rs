@@ -39,6 +39,33 @@ fn test_alma() -> Result<()> {
     })
 }
 
+#[test]
+fn test_atr() -> Result<()> {
+    let mut indicator = indicators::Atr::new(&indicators::MAParams::Sma(indicators::SmaParams {
+        period: 5,
+    }));
+    assert("atr", |inputs, i| {
+        indicator.update(
+            inputs[0][i].parse()?,
+            inputs[1][i].parse()?,
+            inputs[2][i].parse()?,
+        );
+        Ok(vec![indicator.value])
+    })
+}
+
+#[test]
+fn test_bollinger() -> Result<()> {
+    let mut indicator = indicators::Bollinger::new(
+        &indicators::MAParams::Sma(indicators::SmaParams { period: 5 }),
+        2.0,
+    );
+    assert("bollinger", |inputs, i| {
+        indicator.update(inputs[0][i].parse()?);
+        Ok(vec![indicator.upper, indicator.middle, indicator.lower])
+    })
+}
+
 #[test]
 fn test_dema() -> Result<()> {
     let mut indicator = indicators::Dema::new(&indicators::DemaParams { period: 5 });
@@ -79,6 +106,15 @@ fn test_dx() -> Result<()> {
     })
 }
 
+#[test]
+fn test_donchian() -> Result<()> {
+    let mut indicator = indicators::Donchian::new(5);
+    assert("donchian", |inputs, i| {
+        indicator.update(inputs[0][i].parse()?, inputs[1][i].parse()?);
+        Ok(vec![indicator.upper, indicator.lower])
+    })
+}
+
 #[test]
 fn test_ema() -> Result<()> {
     let mut indicator = indicators::Ema::new(&indicators::EmaParams {
@@ -91,6 +127,24 @@ fn test_ema() -> Result<()> {
     })
 }
 
+#[test]
+fn test_ichimoku() -> Result<()> {
+    let mut indicator = indicators::Ichimoku::new(3, 5, 8, 4);
+    assert("ichimoku", |inputs, i| {
+        indicator.update(
+            inputs[0][i].parse()?,
+            inputs[1][i].parse()?,
+            inputs[2][i].parse()?,
+        );
+        Ok(vec![
+            indicator.tenkan,
+            indicator.kijun,
+            indicator.senkou_a,
+            indicator.senkou_b,
+        ])
+    })
+}
+
 #[test]
 fn test_kama() -> Result<()> {
     let mut indicator = indicators::Kama::new(&indicators::KamaParams { period: 4 });
@@ -151,9 +205,8 @@ where
     for i in 0..input_len {
         let values = update(&data.inputs, i)?;
         if i >= offset {
-            for j in 0..data.outputs.len() {
-                let expected: f64 = data.outputs[j][i - offset].parse()?;
-                let value = values[j];
+            for (output, &value) in data.outputs.iter().zip(values.iter()) {
+                let expected: f64 = output[i - offset].parse()?;
                 let diff = f64::abs(value - expected);
                 assert!(
                     diff < 0.001,
@@ -1,10 +1,18 @@
 use juno::{
+    candle_transform::CandleTransformParams,
     indicators::{self, MAParams},
+    position_sizer::{self, PositionSizerParams},
+    slippage::{self, SlippageParams},
     statistics::CoreStatistics,
     stop_loss::{self, StopLossParams},
     strategies::{self, StrategyParams},
     take_profit::{self, TakeProfitParams},
-    trading::{trade, TradeInput, TraderParams, TradingParams, TradingSummary},
+    time_exit::TimeExitParams,
+    trading::{
+        trade, Direction, EntryOrder, ExecutionTiming, ExitTrigger, GapPolicy, Market,
+        ReEntryParams, SessionFilterParams, TradeInput, TraderParams, TradingParams,
+        TradingSummary,
+    },
     Candle, ExchangeInfo, Interval,
 };
 use once_cell::sync::Lazy;
@@ -16,6 +24,14 @@ static EXPECTED_STATS: Lazy<HashMap<String, CoreStatistics>> = Lazy::new(|| {
     serde_json::from_reader(file).expect("unable to deserialize json")
 });
 
+// Statistics captured from this implementation for strategies without an independent reference.
+// They only guard against regressions and do not vouch for the results being correct.
+static SNAPSHOT_STATS: Lazy<HashMap<String, CoreStatistics>> = Lazy::new(|| {
+    let path = "./tests/data/strategies_snapshots.json";
+    let file = File::open(path).expect("unable to open file");
+    serde_json::from_reader(file).expect("unable to deserialize json")
+});
+
 static EXCHANGE_INFO: Lazy<ExchangeInfo> = Lazy::new(|| {
     let path = "./tests/data/binance_exchange_info.json";
     let file = File::open(path).expect("unable to open file");
@@ -38,7 +54,7 @@ fn test_four_week_rule() {
                 smoothing: None,
             }),
         }),
-        "FourWeekRuleParams",
+        &EXPECTED_STATS["FourWeekRuleParams"],
     );
 }
 
@@ -51,7 +67,7 @@ fn test_single_ma() {
                 smoothing: None,
             }),
         }),
-        "SingleMAParams",
+        &EXPECTED_STATS["SingleMAParams"],
     );
 }
 
@@ -70,7 +86,7 @@ fn test_double_ma() {
                 }),
             ),
         }),
-        "DoubleMAParams",
+        &EXPECTED_STATS["DoubleMAParams"],
     );
 }
 
@@ -93,11 +109,82 @@ fn test_triple_ma() {
                 }),
             ),
         }),
-        "TripleMAParams",
+        &EXPECTED_STATS["TripleMAParams"],
+    );
+}
+
+#[test]
+fn test_rsi() {
+    test_strategy(
+        StrategyParams::RsiSignal(strategies::RsiSignalParams {
+            rsi: strategies::RsiParams {
+                period: 14,
+                up_threshold: 70.0,
+                down_threshold: 30.0,
+            },
+            entry: strategies::OscEntry::MeanReversion,
+            exit: strategies::OscExit::Centerline,
+        }),
+        &SNAPSHOT_STATS["RsiSignalParams"],
+    );
+}
+
+#[test]
+fn test_stoch() {
+    test_strategy(
+        StrategyParams::StochSignal(strategies::StochSignalParams {
+            stoch: strategies::StochParams {
+                k_period: 14,
+                k_sma_period: 3,
+                d_sma_period: 3,
+                up_threshold: 80.0,
+                down_threshold: 20.0,
+            },
+            entry: strategies::OscEntry::ThresholdCross,
+            exit: strategies::OscExit::Opposite,
+        }),
+        &SNAPSHOT_STATS["StochSignalParams"],
+    );
+}
+
+#[test]
+fn test_bollinger() {
+    test_strategy(
+        StrategyParams::Bollinger(strategies::BollingerParams {
+            ma: MAParams::Sma(indicators::SmaParams { period: 20 }),
+            multiplier: 2.0,
+            mode: strategies::BollingerMode::Breakout,
+            squeeze: Some(0.3),
+        }),
+        &SNAPSHOT_STATS["BollingerParams"],
+    );
+}
+
+#[test]
+fn test_turtle() {
+    test_strategy(
+        StrategyParams::Turtle(strategies::TurtleParams {
+            periods: (20, 10),
+            skip_after_winner: true,
+        }),
+        &SNAPSHOT_STATS["TurtleParams"],
+    );
+}
+
+#[test]
+fn test_ichimoku() {
+    test_strategy(
+        StrategyParams::Ichimoku(strategies::IchimokuParams {
+            periods: (9, 26, 52),
+            displacement: 26,
+            mode: strategies::IchimokuMode::PriceCloud,
+            chikou: true,
+        }),
+        &SNAPSHOT_STATS["IchimokuParams"],
     );
 }
 
-fn test_strategy(strategy: StrategyParams, name: &str) {
+fn test_strategy(strategy: StrategyParams, expected: &CoreStatistics) {
     let summary = trade(
         &TradingParams {
             strategy,
@@ -109,10 +196,17 @@ fn test_strategy(strategy: StrategyParams, name: &str) {
                 up_threshold: 0.1,
                 down_threshold: 0.1,
             }),
+            time_exit: TimeExitParams::default(),
             // stop_loss: StopLossParams::Noop(stop_loss::NoopParams {}),
             // take_profit: TakeProfitParams::Noop(take_profit::NoopParams {}),
+            position_sizer: PositionSizerParams::AllIn(position_sizer::AllInParams {}),
+            candle_transform: CandleTransformParams::default(),
             trader: TraderParams {
                 interval: Interval::DAY_MS,
+                direction: Direction::Both,
+                margin_multiplier: 2,
+                re_entry: ReEntryParams::default(),
+                session: SessionFilterParams::default(),
             },
         },
         &TradeInput {
@@ -120,15 +214,22 @@ fn test_strategy(strategy: StrategyParams, name: &str) {
             fees: &EXCHANGE_INFO.fees["eth-btc"],
             filters: &EXCHANGE_INFO.filters["eth-btc"],
             borrow_info: &EXCHANGE_INFO.borrow_info["eth-btc"]["eth"],
-            margin_multiplier: 2,
+            maintenance_margin_level: 1.1,
             quote: 1.0,
-            long: true,
-            short: true,
+            exit_trigger: ExitTrigger::Close,
+            execution_timing: ExecutionTiming::Close,
+            entry_order: EntryOrder::Market,
+            slippage: SlippageParams::Noop(slippage::NoopParams {}),
+            market: Market::Spot,
+            funding_rates: &[],
+            blackouts: &[],
+            gap_policy: GapPolicy::default(),
+            record: false,
         },
     );
     // dump_summary(&summary);
     let output = CoreStatistics::compose(&summary);
-    assert_stats(&output, &EXPECTED_STATS[name]);
+    assert_stats(&output, expected);
 }
 
 fn assert_approx(left: f64, right: f64) {
@@ -165,6 +266,7 @@ fn assert_stats(left: &CoreStatistics, right: &CoreStatistics) {
     assert_eq!(left.num_positions_in_loss, right.num_positions_in_loss);
     assert_eq!(left.num_stop_losses, right.num_stop_losses);
     assert_eq!(left.num_take_profits, right.num_take_profits);
+    assert_eq!(left.num_liquidations, right.num_liquidations);
 }
 
 #[allow(dead_code)]
@@ -5,15 +5,9 @@ mod routes;
 use anyhow::Result;
 use axum::{routing::get, Router};
 use juno::clients::juno_core;
-use serde::Serialize;
 use std::{env, net::SocketAddr, sync::Arc};
 use tower_http::cors::CorsLayer;
 
-#[derive(Serialize)]
-struct ErrorResponse {
-    message: String,
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     // Set default log level to info.
@@ -6,14 +6,19 @@ use axum::{
 };
 use itertools::Itertools;
 use juno::{
-    statistics::Statistics,
-    trading::{trade, TradeInput, TradingParams},
-    Candle, ExchangeInfo, Interval, SymbolExt, Timestamp,
+    slippage::SlippageParams,
+    statistics::{PortfolioStatistics, Statistics},
+    trading::{
+        default_maintenance_margin_level, trade, trade_portfolio, Allocation, Blackout, EntryOrder,
+        ExecutionTiming, ExitTrigger, GapPolicy, Market, PortfolioInput, Recording, TradeInput,
+        TradingParams,
+    },
+    Candle, ExchangeInfo, FundingRate, Interval, SymbolExt, Timestamp,
 };
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::error::Error;
 use juno::clients::juno_core;
@@ -26,11 +31,38 @@ struct Params {
     end: Timestamp,
     quote: f64,
     trading: TradingParams,
+    #[serde(default = "default_maintenance_margin_level")]
+    maintenance_margin_level: f64,
+    #[serde(default)]
+    exit_trigger: ExitTrigger,
+    #[serde(default)]
+    execution_timing: ExecutionTiming,
+    #[serde(default)]
+    entry_order: EntryOrder,
+    #[serde(default)]
+    slippage: SlippageParams,
+    #[serde(default)]
+    market: Market,
+    #[serde(default)]
+    funding_rates: HashMap<String, Vec<FundingRate>>,
+    #[serde(default)]
+    blackouts: Vec<Blackout>,
+    #[serde(default)]
+    gap_policy: GapPolicy,
+    #[serde(default)]
+    record: bool,
+    // If set, the symbols are also traded as a single portfolio from a shared `quote`.
+    #[serde(default)]
+    portfolio: Option<Allocation>,
 }
 
 #[derive(Serialize)]
 struct BacktestResult {
     symbol_stats: HashMap<String, Statistics>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    symbol_recordings: HashMap<String, Recording>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    portfolio_stats: Option<PortfolioStatistics>,
 }
 
 pub fn routes() -> Router<Arc<juno_core::Client>> {
@@ -64,6 +96,17 @@ async fn post(
             symbols,
             args.trading,
             args.quote,
+            args.maintenance_margin_level,
+            args.exit_trigger,
+            args.execution_timing,
+            args.entry_order,
+            args.slippage,
+            args.market,
+            args.funding_rates,
+            args.blackouts,
+            args.gap_policy,
+            args.record,
+            args.portfolio,
         )
     })
     .await;
@@ -71,6 +114,7 @@ async fn post(
     Ok((StatusCode::OK, Json(backtest_result)))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn par_backtest(
     exchange_info: ExchangeInfo,
     candles: HashMap<String, HashMap<Interval, Vec<Candle>>>,
@@ -78,27 +122,68 @@ fn par_backtest(
     symbols: Vec<String>,
     trading: TradingParams,
     quote: f64,
+    maintenance_margin_level: f64,
+    exit_trigger: ExitTrigger,
+    execution_timing: ExecutionTiming,
+    entry_order: EntryOrder,
+    slippage: SlippageParams,
+    market: Market,
+    funding_rates: HashMap<String, Vec<FundingRate>>,
+    blackouts: Vec<Blackout>,
+    gap_policy: GapPolicy,
+    record: bool,
+    portfolio: Option<Allocation>,
 ) -> BacktestResult {
     let stats_interval = Interval::DAY_MS;
-    let symbol_stats = symbols
+    let trade_input = |symbol: &String| TradeInput {
+        candles: &candles[symbol][&trading.trader.interval],
+        fees: &exchange_info.fees[symbol],
+        filters: &exchange_info.filters[symbol],
+        borrow_info: &exchange_info.borrow_info[symbol][symbol.base_asset()],
+        maintenance_margin_level,
+        quote,
+        exit_trigger,
+        execution_timing,
+        entry_order,
+        slippage,
+        market,
+        funding_rates: funding_rates.get(symbol).map_or(&[], Vec::as_slice),
+        blackouts: &blackouts,
+        gap_policy,
+        record,
+    };
+    let (symbol_stats, symbol_recordings): (HashMap<_, _>, Vec<_>) = symbols
         .par_iter()
         .map(|symbol| {
-            let summary = trade(
-                &trading,
-                &TradeInput {
-                    candles: &candles[symbol][&trading.trader.interval],
-                    fees: &exchange_info.fees[symbol],
-                    filters: &exchange_info.filters[symbol],
-                    borrow_info: &exchange_info.borrow_info[symbol][symbol.base_asset()],
-                    margin_multiplier: 2,
-                    quote,
-                    long: true,
-                    short: true,
-                },
-            );
+            let summary = trade(&trading, &trade_input(symbol));
+            if let Some(halt) = summary.halted {
+                warn!("{} halted at {:?}: {}", symbol, halt.time, halt.error);
+            }
             let stats = Statistics::compose(&summary, symbol, &prices, stats_interval);
-            (symbol.clone(), stats)
+            (
+                (symbol.clone(), stats),
+                summary
+                    .recording
+                    .map(|recording| (symbol.clone(), recording)),
+            )
         })
-        .collect();
-    BacktestResult { symbol_stats }
+        .unzip();
+    let portfolio_stats = portfolio.map(|allocation| {
+        let inputs: Vec<_> = symbols.iter().map(trade_input).collect();
+        let summary = trade_portfolio(
+            &trading,
+            &PortfolioInput {
+                symbols: &symbols,
+                inputs: &inputs,
+                quote,
+                allocation,
+            },
+        );
+        PortfolioStatistics::compose(&summary, stats_interval)
+    });
+    BacktestResult {
+        symbol_stats,
+        symbol_recordings: symbol_recordings.into_iter().flatten().collect(),
+        portfolio_stats,
+    }
 }
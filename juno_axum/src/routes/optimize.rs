@@ -5,12 +5,15 @@ use juno::{
     genetics::{
         crossover, mutation, reinsertion, selection, Chromosome, GeneticAlgorithm, Individual,
     },
-    statistics::Statistics,
+    slippage::SlippageParams,
+    statistics::{PortfolioStatistics, Statistics},
     trading::{
-        trade, BasicEvaluation, BasicEvaluationInput, EvaluationAggregation, EvaluationStatistic,
-        TradeInput, TradingParams, TradingParamsContext,
+        default_maintenance_margin_level, trade, trade_portfolio, Allocation, BasicEvaluation,
+        BasicEvaluationInput, Blackout, EntryOrder, EvaluationAggregation, EvaluationStatistic,
+        ExecutionTiming, ExitTrigger, GapPolicy, Market, PortfolioInput, TradeInput, TradingParams,
+        TradingParamsContext,
     },
-    Candle, ExchangeInfo, Interval, SymbolExt, Timestamp,
+    Candle, ExchangeInfo, FundingRate, Interval, SymbolExt, Timestamp,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
@@ -29,6 +32,29 @@ struct Params {
     start: Timestamp,
     end: Timestamp,
     quote: f64,
+    #[serde(default = "default_maintenance_margin_level")]
+    maintenance_margin_level: f64,
+    #[serde(default)]
+    exit_trigger: ExitTrigger,
+    #[serde(default)]
+    execution_timing: ExecutionTiming,
+    #[serde(default)]
+    entry_order: EntryOrder,
+    #[serde(default)]
+    slippage: SlippageParams,
+    #[serde(default)]
+    market: Market,
+    #[serde(default)]
+    funding_rates: HashMap<String, Vec<FundingRate>>,
+    #[serde(default)]
+    blackouts: Vec<Blackout>,
+    #[serde(default)]
+    gap_policy: GapPolicy,
+    #[serde(default)]
+    penalize_halted: bool,
+    // If set, training symbols are optimized as a single portfolio from a shared `quote`.
+    #[serde(default)]
+    portfolio: Option<Allocation>,
     training_symbols: Vec<String>,
 
     validation_symbols: Vec<String>,
@@ -57,6 +83,10 @@ struct GenerationOutput {
 struct IndividualStats {
     individual: Individual<TradingParams>,
     symbol_stats: HashMap<String, Statistics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    training_portfolio_stats: Option<PortfolioStatistics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validation_portfolio_stats: Option<PortfolioStatistics>,
 }
 
 #[derive(Serialize)]
@@ -126,6 +156,17 @@ fn par_optimize(
             start: args.start,
             end: args.end,
             quote: args.quote,
+            maintenance_margin_level: args.maintenance_margin_level,
+            exit_trigger: args.exit_trigger,
+            execution_timing: args.execution_timing,
+            entry_order: args.entry_order,
+            slippage: args.slippage,
+            market: args.market,
+            funding_rates: &args.funding_rates,
+            blackouts: &args.blackouts,
+            gap_policy: args.gap_policy,
+            penalize_halted: args.penalize_halted,
+            portfolio: args.portfolio,
             evaluation_statistic: args.evaluation_statistic,
             evaluation_aggregation: args.evaluation_aggregation,
         }),
@@ -173,31 +214,53 @@ fn par_optimize(
                 .hall_of_fame
                 .into_iter()
                 .map(|ind| {
+                    let trade_input = |symbol: &String| TradeInput {
+                        candles: &candles[symbol][&ind.chromosome.trader.interval],
+                        fees: &exchange_info.fees[symbol],
+                        filters: &exchange_info.filters[symbol],
+                        borrow_info: &exchange_info.borrow_info[symbol][symbol.base_asset()],
+                        maintenance_margin_level: args.maintenance_margin_level,
+                        quote: args.quote,
+                        exit_trigger: args.exit_trigger,
+                        execution_timing: args.execution_timing,
+                        entry_order: args.entry_order,
+                        slippage: args.slippage,
+                        market: args.market,
+                        funding_rates: args.funding_rates.get(symbol).map_or(&[], Vec::as_slice),
+                        blackouts: &args.blackouts,
+                        gap_policy: args.gap_policy,
+                        record: false,
+                    };
                     let symbol_stats: HashMap<_, _> = args
                         .iter_symbols()
                         .unique()
                         .map(|symbol| {
-                            let summary = trade(
-                                &ind.chromosome,
-                                &TradeInput {
-                                    candles: &candles[symbol][&ind.chromosome.trader.interval],
-                                    fees: &exchange_info.fees[symbol],
-                                    filters: &exchange_info.filters[symbol],
-                                    borrow_info: &exchange_info.borrow_info[symbol]
-                                        [symbol.base_asset()],
-                                    margin_multiplier: 2,
-                                    quote: args.quote,
-                                    long: true,
-                                    short: true,
-                                },
-                            );
+                            let summary = trade(&ind.chromosome, &trade_input(symbol));
                             let stats =
                                 Statistics::compose(&summary, symbol, prices, stats_interval);
                             (symbol.clone(), stats)
                         })
                         .collect();
+                    let portfolio_stats = |symbols: &[String]| {
+                        let allocation = args.portfolio.filter(|_| !symbols.is_empty());
+                        allocation.map(|allocation| {
+                            let inputs: Vec<_> = symbols.iter().map(trade_input).collect();
+                            let summary = trade_portfolio(
+                                &ind.chromosome,
+                                &PortfolioInput {
+                                    symbols,
+                                    inputs: &inputs,
+                                    quote: args.quote,
+                                    allocation,
+                                },
+                            );
+                            PortfolioStatistics::compose(&summary, stats_interval)
+                        })
+                    };
                     IndividualStats {
                         symbol_stats,
+                        training_portfolio_stats: portfolio_stats(&args.training_symbols),
+                        validation_portfolio_stats: portfolio_stats(&args.validation_symbols),
                         individual: ind,
                     }
                 })
@@ -82,7 +82,7 @@ pub fn derive_chromosome(input: TokenStream) -> TokenStream {
             if let Type::Path(type_path) = field_ty {
                 if let Some(type_ident) = type_path.path.get_ident() {
                     // Is generic type.
-                    if generic_ty_idents.iter().any(|&ident| ident == type_ident) {
+                    if generic_ty_idents.contains(&type_ident) {
                         return quote! { #field_ty };
                     } else {
                         let type_ident = format_ident!("{}Context", type_ident);
@@ -178,7 +178,7 @@ pub fn derive_chromosome(input: TokenStream) -> TokenStream {
 
     let output = quote! {
         #(#ctx_attrs)*
-        #[derive(Clone, Default, Deserialize, Serialize)]
+        #[derive(Clone, Debug, Default, Deserialize, Serialize)]
         #ctx_vis struct #ctx_name #ctx_generic_ty {
             #(
                 #(#ctx_field_attrs)*
@@ -79,7 +79,7 @@ pub fn derive_chromosome_enum(input: TokenStream) -> TokenStream {
             }
         }
 
-        #[derive(Clone, Deserialize, Serialize)]
+        #[derive(Clone, Debug, Deserialize, Serialize)]
         #[serde(tag = "type")]
         pub enum #ctx_name {
             None,